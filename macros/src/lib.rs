@@ -0,0 +1,77 @@
+//! Procedural macros used by the `const_map` crate when its `proc-macro` feature is enabled. They
+//! aren't meant to be used directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenTree;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Attribute, Expr, Token};
+
+/// The input of [`normalize_entries!`]: the path to the `const_map` crate, the state to pass back
+/// to it, and the entries of the map.
+struct Input {
+    krate: TokenTree,
+    state: TokenTree,
+    entries: Vec<Entry>,
+}
+
+struct Entry {
+    attrs: Vec<Attribute>,
+    key: Expr,
+    value: Expr,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let krate = input.parse()?;
+        let state = input.parse()?;
+        let mut entries = Vec::new();
+        while !input.is_empty() {
+            let mut attrs = Attribute::parse_outer(input)?;
+            // Doc comments are dropped, since they don't do anything on expressions.
+            attrs.retain(|attr| !attr.path().is_ident("doc"));
+            let key = input.parse()?;
+            input.parse::<Token![=>]>()?;
+            let value = input.parse()?;
+            entries.push(Entry { attrs, key, value });
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        Ok(Self {
+            krate,
+            state,
+            entries,
+        })
+    }
+}
+
+/// Does the same as the `@entries` phase of `const_map_impl!`, normalizing the entries of a map to
+/// `[attributes] key => value,`, but all at once instead of a few at a time, so that maps with
+/// thousands of entries don't run into the recursion limit.
+#[doc(hidden)]
+#[proc_macro]
+pub fn normalize_entries(input: TokenStream) -> TokenStream {
+    let Input {
+        krate,
+        state,
+        mut entries,
+    } = syn::parse_macro_input!(input as Input);
+    // A last entry of `_ => value` is passed on as it is, for `const_map_impl!` to deal with it,
+    // along with any mistakes like a `_` entry which isn't the last one.
+    let rest = match entries.iter().position(|entry| matches!(entry.key, Expr::Infer(_))) {
+        Some(i) => entries.split_off(i),
+        None => Vec::new(),
+    };
+    let entries = entries.iter().map(|Entry { attrs, key, value }| {
+        quote! { [#(#attrs)*] #key => #value, }
+    });
+    let rest = rest.iter().map(|Entry { attrs, key, value }| {
+        quote! { #(#attrs)* #key => #value, }
+    });
+    quote! {
+        #krate::const_map_impl!(@entries #state [#(#entries)*] [] #(#rest)*);
+    }
+    .into()
+}
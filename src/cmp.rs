@@ -0,0 +1,112 @@
+//! Comparison functions usable in a const context, for types whose `PartialEq` and `PartialOrd`
+//! implementations aren't.
+
+// The syntax for const trait bounds doesn't even parse on a stable compiler, so it's kept in a
+// separate file.
+#[cfg(feature = "const-trait")]
+mod const_trait;
+#[cfg(feature = "const-trait")]
+pub use const_trait::partial_eq;
+
+/// Compares two byte strings for equality.
+pub const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Checks whether one byte string sorts before another.
+pub const fn bytes_lt(a: &[u8], b: &[u8]) -> bool {
+    let mut i = 0;
+    while i < a.len() && i < b.len() {
+        if a[i] != b[i] {
+            return a[i] < b[i];
+        }
+        i += 1;
+    }
+    a.len() < b.len()
+}
+
+/// Compares two strings for equality.
+pub const fn str_eq(a: &str, b: &str) -> bool {
+    bytes_eq(a.as_bytes(), b.as_bytes())
+}
+
+/// Checks whether one string sorts before another, comparing them byte by byte.
+pub const fn str_lt(a: &str, b: &str) -> bool {
+    bytes_lt(a.as_bytes(), b.as_bytes())
+}
+
+/// Compares two strings for equality, ignoring ASCII case.
+pub const fn str_eq_ignore_case(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if !a[i].eq_ignore_ascii_case(&b[i]) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Checks whether one string sorts before another, comparing them byte by byte and ignoring ASCII
+/// case.
+pub const fn str_lt_ignore_case(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut i = 0;
+    while i < a.len() && i < b.len() {
+        let (x, y) = (a[i].to_ascii_lowercase(), b[i].to_ascii_lowercase());
+        if x != y {
+            return x < y;
+        }
+        i += 1;
+    }
+    a.len() < b.len()
+}
+
+/// Maps a float to an integer which sorts the same way as the float does in the total order of
+/// `f32::total_cmp`, in which `-0.0` comes before `0.0` and `NaN`s come before or after everything
+/// else depending on their sign.
+const fn f32_total_key(x: f32) -> i32 {
+    let bits = x.to_bits() as i32;
+    bits ^ (((bits >> 31) as u32) >> 1) as i32
+}
+
+/// Compares two floats for equality in the total order of `f32::total_cmp`, so that a `NaN` is
+/// equal to itself but `-0.0` isn't equal to `0.0`.
+pub const fn f32_eq(a: f32, b: f32) -> bool {
+    f32_total_key(a) == f32_total_key(b)
+}
+
+/// Checks whether one float comes before another in the total order of `f32::total_cmp`.
+pub const fn f32_lt(a: f32, b: f32) -> bool {
+    f32_total_key(a) < f32_total_key(b)
+}
+
+/// Like [`f32_total_key`], but for `f64`.
+const fn f64_total_key(x: f64) -> i64 {
+    let bits = x.to_bits() as i64;
+    bits ^ (((bits >> 63) as u64) >> 1) as i64
+}
+
+/// Like [`f32_eq`], but for `f64`.
+pub const fn f64_eq(a: f64, b: f64) -> bool {
+    f64_total_key(a) == f64_total_key(b)
+}
+
+/// Like [`f32_lt`], but for `f64`.
+pub const fn f64_lt(a: f64, b: f64) -> bool {
+    f64_total_key(a) < f64_total_key(b)
+}
@@ -0,0 +1,6 @@
+//! Comparisons using const trait implementations, for the `const-trait` feature.
+
+/// Compares two values with their const `PartialEq` implementation.
+pub const fn partial_eq<T: [const] PartialEq>(a: &T, b: &T) -> bool {
+    PartialEq::eq(a, b)
+}
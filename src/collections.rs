@@ -0,0 +1,14 @@
+//! Collection types used by the functions generated by some options, which are only available with
+//! some of the crate's features.
+
+#[cfg(feature = "std")]
+pub use std::collections::HashMap;
+
+#[cfg(feature = "alloc")]
+pub use alloc::collections::BTreeMap;
+
+#[cfg(feature = "alloc")]
+pub use alloc::string::String;
+
+#[cfg(feature = "hashbrown")]
+pub use hashbrown::HashMap as HashbrownMap;
@@ -0,0 +1,29 @@
+use core::fmt;
+
+/// The error returned by conversions and `try_get` functions generated from a const map, when the
+/// key isn't in the map.
+///
+/// It holds the key that was looked up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NotFound<K>(pub K);
+
+impl<K: fmt::Debug> fmt::Display for NotFound<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "key {:?} not found", self.0)
+    }
+}
+
+impl<K: fmt::Debug> core::error::Error for NotFound<K> {}
+
+/// The error returned by `FromStr` implementations generated from a const map with string keys,
+/// when the string isn't one of the keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseError;
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("string not found in const map")
+    }
+}
+
+impl core::error::Error for ParseError {}
@@ -0,0 +1,323 @@
+//! A perfect hash table built at compile time, for maps declared as `hashed`.
+//!
+//! This uses the "hash and displace" technique: each key's hash picks a bucket, and each bucket
+//! has a displacement value which is chosen such that every key in the bucket lands on a
+//! different, otherwise unoccupied slot. Looking up a key then takes a fixed amount of work, no
+//! matter how many entries the map has.
+//!
+//! There are `N` buckets and `2 * N` slots for a map with `N` entries.
+
+const EMPTY: u32 = u32::MAX;
+
+/// How many displacement values to try for a bucket before giving up and trying another seed.
+const MAX_DISPLACEMENT: u32 = 1 << 16;
+
+/// How many seeds to try before giving up completely.
+const MAX_SEEDS: u64 = 64;
+
+/// A perfect hash table mapping the hashes of a map's keys to their index in the map.
+pub struct Table<const N: usize> {
+    seed: u64,
+    displacements: [u32; N],
+    slots: [[u32; N]; 2],
+}
+
+/// Mixes the bits of a value, so that all of them affect all of the result (this is the final step
+/// of SplitMix64).
+const fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+const fn bucket(hash: u64, seed: u64, n: usize) -> usize {
+    (mix(hash ^ seed) % n as u64) as usize
+}
+
+/// Keys with the same hash always end up in the same bucket, and can never be placed. Since it's
+/// enough to check within each bucket, that's done just before placing it.
+const fn check_distinct<const N: usize>(
+    hashes: &[u64; N],
+    grouped: &[usize; N],
+    (start, end): (usize, usize),
+) {
+    let mut i = start;
+    while i < end {
+        let mut j = i + 1;
+        while j < end {
+            if hashes[grouped[i]] == hashes[grouped[j]] {
+                panic!("two keys of a hashed const_map have the same hash");
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+}
+
+const fn slot(hash: u64, seed: u64, displacement: u32, n: usize) -> usize {
+    let x = mix(hash.wrapping_add(seed).wrapping_add((displacement as u64 + 1) << 32));
+    (x % (2 * n as u64)) as usize
+}
+
+impl<const N: usize> Table<N> {
+    /// Builds the table from the hashes of the keys, which must all be different.
+    pub const fn new(hashes: [u64; N]) -> Self {
+        let mut seed = 0;
+        while seed < MAX_SEEDS {
+            if let Some(table) = Self::try_seed(&hashes, seed) {
+                return table;
+            }
+            seed += 1;
+        }
+        panic!(
+            "couldn't build a perfect hash table for a hashed const_map, after trying every seed; \
+             use another lookup algorithm, such as `sorted`, instead"
+        );
+    }
+
+    const fn try_seed(hashes: &[u64; N], seed: u64) -> Option<Self> {
+        let mut table = Table {
+            seed,
+            displacements: [0; N],
+            slots: [[EMPTY; N]; 2],
+        };
+
+        // Group the keys by bucket.
+        let mut buckets = [0; N];
+        let mut sizes = [0; N];
+        let mut i = 0;
+        while i < N {
+            buckets[i] = bucket(hashes[i], seed, N);
+            sizes[buckets[i]] += 1;
+            i += 1;
+        }
+        let mut starts = [0; N];
+        let mut b = 1;
+        while b < N {
+            starts[b] = starts[b - 1] + sizes[b - 1];
+            b += 1;
+        }
+        let mut grouped = [0; N];
+        let mut filled = [0; N];
+        let mut i = 0;
+        while i < N {
+            let b = buckets[i];
+            grouped[starts[b] + filled[b]] = i;
+            filled[b] += 1;
+            i += 1;
+        }
+
+        // Place the buckets with the most keys first, since they're the hardest to place, while
+        // most of the slots are still free.
+        let mut max_size = 0;
+        let mut b = 0;
+        while b < N {
+            if sizes[b] > max_size {
+                max_size = sizes[b];
+            }
+            b += 1;
+        }
+        let mut size = max_size;
+        while size > 0 {
+            let mut b = 0;
+            while b < N {
+                if sizes[b] == size {
+                    let keys = (starts[b], starts[b] + size);
+                    check_distinct(hashes, &grouped, keys);
+                    let mut displacement = 0;
+                    while !table.try_place(hashes, &grouped, keys, displacement) {
+                        displacement += 1;
+                        if displacement == MAX_DISPLACEMENT {
+                            return None;
+                        }
+                    }
+                    table.displacements[b] = displacement;
+                }
+                b += 1;
+            }
+            size -= 1;
+        }
+        Some(table)
+    }
+
+    /// Tries to put the keys `grouped[start..end]`, which are all in the same bucket, into free
+    /// slots using the given displacement.
+    const fn try_place(
+        &mut self,
+        hashes: &[u64; N],
+        grouped: &[usize; N],
+        (start, end): (usize, usize),
+        displacement: u32,
+    ) -> bool {
+        let mut i = start;
+        while i < end {
+            let s = slot(hashes[grouped[i]], self.seed, displacement, N);
+            if self.slots[s / N][s % N] != EMPTY {
+                // Undo the keys placed so far.
+                let mut j = start;
+                while j < i {
+                    let s = slot(hashes[grouped[j]], self.seed, displacement, N);
+                    self.slots[s / N][s % N] = EMPTY;
+                    j += 1;
+                }
+                return false;
+            }
+            self.slots[s / N][s % N] = grouped[i] as u32;
+            i += 1;
+        }
+        true
+    }
+
+    /// Returns the index in the map of the only key which could have the given hash, if any.
+    /// The caller still needs to check that the key matches.
+    #[inline]
+    pub const fn get(&self, hash: u64) -> Option<usize> {
+        if N == 0 {
+            return None;
+        }
+        let displacement = self.displacements[bucket(hash, self.seed, N)];
+        let s = slot(hash, self.seed, displacement, N);
+        match self.slots[s / N][s % N] {
+            EMPTY => None,
+            i => Some(i as usize),
+        }
+    }
+}
+
+/// The longest message [`collision`] makes; the keys are cut short to fit.
+const MESSAGE_LEN: usize = 256;
+
+/// A message built at compile time, since a panic in a const context can only format a single
+/// `&str`.
+struct Message {
+    bytes: [u8; MESSAGE_LEN],
+    len: usize,
+}
+
+impl Message {
+    const fn push(&mut self, s: &str) {
+        let bytes = s.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() && self.len < MESSAGE_LEN {
+            self.bytes[self.len] = bytes[i];
+            self.len += 1;
+            i += 1;
+        }
+    }
+
+    const fn as_str(&self) -> &str {
+        // Cutting the message short can split a character, which is dropped.
+        let mut len = self.len;
+        loop {
+            match core::str::from_utf8(self.bytes.split_at(len).0) {
+                Ok(s) => return s,
+                Err(_) => len -= 1,
+            }
+        }
+    }
+}
+
+/// Panics with a message naming two keys of a hashed map which have the same hash, even though
+/// they're different, so that the map can't be hashed.
+#[doc(hidden)]
+pub const fn collision(name: &str, a: &str, b: &str) -> ! {
+    let mut message = Message {
+        bytes: [0; MESSAGE_LEN],
+        len: 0,
+    };
+    message.push("keys `");
+    message.push(a);
+    message.push("` and `");
+    message.push(b);
+    message.push("` of hashed const_map ");
+    message.push(name);
+    message.push(" have the same hash; use another lookup algorithm, such as `sorted`, instead");
+    panic!("{}", message.as_str())
+}
+
+/// Hashes an integer key, widened to 128 bits, to 64 bits without losing its upper half.
+///
+/// Narrower keys keep their lower 64 bits as is, which are all there is to them: their upper half
+/// is either zero, or all ones for negative keys. The upper half is spread out such that all ones
+/// become just the lowest bit, which keeps negative keys negative and so apart from the others.
+pub const fn hash_wide(key: u128) -> u64 {
+    let high = (key >> 64) as u64;
+    key as u64 ^ high ^ (high << 1)
+}
+
+/// Hashes a byte string with FNV-1a, which is simple enough to do in a const context.
+pub const fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hash = 0xcbf29ce484222325;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+        i += 1;
+    }
+    hash
+}
+
+/// Hashes a string's bytes with FNV-1a.
+pub const fn hash_str(s: &str) -> u64 {
+    hash_bytes(s.as_bytes())
+}
+
+/// Like [`hash_str`], but ignoring ASCII case, so that strings which only differ in case have the
+/// same hash.
+pub const fn hash_str_ignore_case(s: &str) -> u64 {
+    let bytes = s.as_bytes();
+    let mut hash = 0xcbf29ce484222325;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i].to_ascii_lowercase() as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+        i += 1;
+    }
+    hash
+}
+
+/// Checks that all the hashes are different, by sorting them, which is much quicker than comparing
+/// every pair of keys for a big map.
+pub const fn all_distinct<const N: usize>(mut hashes: [u64; N]) -> bool {
+    // Heapsort, since it needs neither recursion nor extra space.
+    let mut end = N;
+    let mut i = N / 2;
+    while i > 0 {
+        i -= 1;
+        sift_down(&mut hashes, i, end);
+    }
+    while end > 1 {
+        end -= 1;
+        let top = hashes[0];
+        hashes[0] = hashes[end];
+        hashes[end] = top;
+        sift_down(&mut hashes, 0, end);
+    }
+    let mut i = 1;
+    while i < N {
+        if hashes[i - 1] == hashes[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn sift_down<const N: usize>(heap: &mut [u64; N], mut root: usize, end: usize) {
+    loop {
+        let mut child = 2 * root + 1;
+        if child >= end {
+            return;
+        }
+        if child + 1 < end && heap[child] < heap[child + 1] {
+            child += 1;
+        }
+        if heap[root] >= heap[child] {
+            return;
+        }
+        let parent = heap[root];
+        heap[root] = heap[child];
+        heap[child] = parent;
+        root = child;
+    }
+}
@@ -0,0 +1,12 @@
+/// The entries of a const map declared as `interned`, where each distinct value is only stored
+/// once, in `values`, and the entries hold the index of their value instead of the value itself.
+///
+/// The value for `entries[i].0` is `values[entries[i].1 as usize]`. The values are in the order
+/// they first appear in the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interned<K, V, const N: usize, const D: usize> {
+    /// The keys of the map, in order, each with the index of its value.
+    pub entries: [(K, u32); N],
+    /// The distinct values of the map.
+    pub values: [V; D],
+}
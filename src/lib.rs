@@ -1,8 +1,42 @@
 #![doc = include_str!("../README.md")]
 #![deny(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "const-trait", feature(const_trait_impl, const_cmp))]
+#![cfg_attr(feature = "const-trait", doc(test(attr(feature(const_trait_impl)))))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[doc(hidden)]
+pub mod cmp;
+#[doc(hidden)]
+pub mod collections;
+mod error;
+#[doc(hidden)]
+pub mod hash;
+mod interned;
+#[cfg(feature = "serde")]
+mod serialize;
+mod split;
+#[doc(hidden)]
+pub mod total;
+
+#[cfg(feature = "proc-macro")]
+#[doc(hidden)]
+pub use const_map_macros::normalize_entries;
+pub use error::{NotFound, ParseError};
+pub use interned::Interned;
+#[cfg(feature = "serde")]
+pub use serialize::Serializable;
+#[cfg(feature = "json")]
+#[doc(hidden)]
+pub use serialize::to_json;
+pub use split::Split;
 
 /// Define a const map and a const lookup function as associated items of a struct.
 ///
+/// To define them at module level instead, use [`free_const_map!`], which has the same syntax.
+///
 /// The syntax is:
 /// ```no_run
 /// use const_map::const_map;
@@ -11,7 +45,7 @@
 /// # type KeyType = i32;
 /// # type ValueType = i32;
 /// # const key1: i32 = 0;
-/// # const key2: i32 = 0;
+/// # const key2: i32 = 1;
 /// # const value1: i32 = 0;
 /// # const value2: i32 = 0;
 ///
@@ -19,106 +53,5926 @@
 ///
 /// impl YourStruct {
 ///     const_map!(
-///         // The name of the associated constant holding the map.
+///         // The name of the associated constant holding the map, optionally preceded by
+///         // `bijective`, by `ordered` or `multi`, and then by a keyword choosing how lookups are
+///         // done: `sorted`, `hashed`, `fnv`, `jump` or `match` (see below).
 ///         // It will have type `[(KeyType, ValueType); N]` where `N` is the number of elements.
+///         // The name can be followed by the number of elements in brackets, like `NAME[2]`, in
+///         // which case it's a compile error if the map has a different number of elements. It
+///         // can also be preceded by `split` or `interned` (see below), and with
+///         // `free_const_map!`, by `static`.
 ///         NAME,
 ///
 ///         // The name of the lookup function.
-///         // It will have signature `const fn(k: KeyType) -> Option<ValueType>`.
+///         // It will have signature `const fn(k: KeyType) -> Option<ValueType>`, unless it is
+///         // written as `&lookup()`, in which case it will return `Option<&'static ValueType>`
+///         // instead, which means the value type doesn't need to be `Copy`.
+///         // It can be followed by `using eq_fn`, where `eq_fn` is a path to a
+///         // `const fn(&KeyType, &KeyType) -> bool` which is used to compare keys instead of `==`.
+///         // Or it can be followed by `or DEFAULT`, where `DEFAULT` is a constant expression of
+///         // type `ValueType`, in which case it will return `ValueType`, giving `DEFAULT` for keys
+///         // which aren't in the map. Or it can be followed by `normalize norm_fn`, where
+///         // `norm_fn` is a path to a `const fn(KeyType) -> KeyType` which is applied to the key
+///         // before looking it up, such as one which trims whitespace. Or it can be followed by
+///         // `fallback other_lookup`, where `other_lookup` is a path to a
+///         // `const fn(KeyType) -> Option<ValueType>` which is called for keys which aren't in
+///         // the map, such as the lookup function of another map.
+///         // It can be preceded by attributes, such as `#[inline]`, which are put on the function,
+///         // and its name can be followed by the lookup algorithm in brackets, such as `[binary]`
+///         // (see below).
 ///         lookup(),
 ///
+///         // Optionally, the name of a reverse lookup function, which finds the key for a value.
+///         // It will have signature `const fn(v: ValueType) -> Option<KeyType>`, and if several
+///         // keys have the same value, it returns the first one. This is required if the map is
+///         // `bijective`, in which case values must be unique, the same as keys, and a duplicate
+///         // value is a compile error. If it is preceded by `sorted`, the values must be in
+///         // ascending order instead, which is checked at compile time, and it does a binary
+///         // search. Like the lookup function, it can be followed by `using eq_fn` to compare
+///         // values with a `const fn(&ValueType, &ValueType) -> bool`, unless the map is
+///         // `bijective`.
+///         reverse_lookup(),
+///
 ///         // Specify the types of the keys and values of the map.
 ///         (KeyType => ValueType) {
 ///
 ///             // Followed by the entries of the map, which must be expressions that can be
-///             // evaluated in a const (compile-time) context.
+///             // evaluated in a const (compile-time) context. Keys must be unique; a duplicate
+///             // key is a compile error. Entries can have doc comments and attributes such as
+///             // `#[allow(...)]`; entries disabled by `#[cfg(...)]` are left out of the map.
 ///             key1 => value1,
 ///             key2 => value2,
 ///             // etc.
-///         }
+///
+///             // The last entry can be `_ => DEFAULT` instead, which isn't part of the map, but
+///             // gives the value for keys which aren't in it, the same as `lookup() or DEFAULT`.
+///         },
+///
+///         // Optionally, a list of extra items to generate, each written as `kind: name()` for a
+///         // function or `kind: NAME` for an associated constant. The available kinds are
+///         // listed below. Like the lookup functions, each can be preceded by attributes.
+///         contains_key: contains(),
+///         len: LEN,
 ///     );
 ///
 ///     // ...
 /// }
 /// ```
+///
+/// Several maps can be defined with one use of the macro, by separating them with `;`:
+///
+/// ```
+/// use const_map::const_map;
+///
+/// struct Tables;
+///
+/// impl Tables {
+///     const_map! {
+///         DIGITS, digit(), (char => u8) {
+///             '0' => 0,
+///             '1' => 1,
+///         }, contains_key: is_digit();
+///
+///         sorted NAMES, name(), (u8 => &'static str) {
+///             0 => "zero",
+///             1 => "one",
+///         };
+///     }
+/// }
+///
+/// assert_eq!(Tables::name(Tables::digit('1').unwrap()), Some("one"));
+/// ```
+///
+/// By default, the lookup function does a linear search of the map. If the map is declared as
+/// `sorted NAME`, the keys must instead be written in ascending order, and the lookup function does
+/// a binary search. This requires the key type to support `<` in a const context, and keys which
+/// are out of order (or duplicated) are a compile error.
+///
+/// Declaring the map as `ordered NAME` checks the keys are in ascending order in the same way,
+/// without changing how lookups are done. This is useful for keeping a big map easy to read.
+///
+/// If the map is declared as `hashed NAME`, a perfect hash table for the keys is built at compile
+/// time, and the lookup function uses it to find the key's entry after hashing it just once, no
+/// matter how big the map is. This is the fastest option for large maps, but it only works for
+/// keys which can be converted to `u128` with `as` (integers, `char` and `bool`), and for strings
+/// and byte strings, which are hashed with FNV-1a. Different keys which have the same hash are a
+/// compile error naming both of them.
+///
+/// If the map is declared as `fnv NAME`, the hash of each key is worked out at compile time
+/// instead, and kept alongside the map. The lookup function hashes the key it's given once, and
+/// then goes through the hashes in order, only comparing the keys in full when the hashes match.
+/// This is still a linear search, but for maps with many string keys, it saves comparing the key
+/// byte by byte with every one of them. Unlike `hashed` maps, keys with the same hash are fine. It
+/// works for the same key types as `hashed` maps.
+///
+/// Entries are parsed one at a time, so maps with more than about a hundred entries run into the
+/// compiler's recursion limit. Enabling the `proc-macro` feature parses them with a procedural
+/// macro instead, which has no such limit, without changing the syntax. Otherwise, the limit can
+/// be raised with `#![recursion_limit = "..."]` in the crate which defines the map.
+///
+/// If the map is declared as `jump NAME`, the keys must be consecutive integers in ascending order,
+/// such as `0, 1, 2, 3`, and the lookup function works out where the key is in the map by
+/// subtracting the first key from it. This is the fastest option of all, and keys which aren't
+/// consecutive are a compile error. Apart from integers, it works for `char`, `bool` and enum keys.
+///
+/// If the map is declared as `match NAME`, the lookup function is a `match` on the key with an arm
+/// for each entry, which the compiler can turn into a jump table or whatever else suits the keys
+/// best. This means the keys must be literals of a type which can be matched on, such as integers,
+/// `char` and `bool`. Like the arms of a `match`, the last entry can be `_ => DEFAULT`, in which
+/// case the lookup function returns `ValueType`, giving `DEFAULT` for any other key. This works
+/// with the other algorithms too.
+///
+/// ```
+/// use const_map::const_map;
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// enum Class { Vowel, Space, Other }
+///
+/// struct Letters;
+///
+/// impl Letters {
+///     const_map!(match MAP, class(), (char => Class) {
+///         'a' => Class::Vowel,
+///         'e' => Class::Vowel,
+///         ' ' => Class::Space,
+///         _ => Class::Other,
+///     });
+/// }
+///
+/// assert_eq!(Letters::class('e'), Class::Vowel);
+/// assert_eq!(Letters::class('z'), Class::Other);
+/// assert_eq!(Letters::MAP.len(), 3);
+/// ```
+///
+/// The algorithm can also be chosen in brackets after the name of the lookup function, as in
+/// `lookup() [binary]`, which is handy for trying out the same map with each of them. The choices
+/// are `linear`, `binary` (the same as `sorted`), `hashed`, `fnv`, `jump` and `match`. In short,
+/// linear search works for any keys, but takes longer the more entries there are; `fnv` is a linear
+/// search which mostly compares hashes instead of strings; binary search needs the keys in order,
+/// and takes much less time for big maps; `hashed` and `jump` take the same time no matter how big
+/// the map is, but only work for some keys; and `match` leaves it up to the compiler, but needs
+/// literal keys.
+///
+/// ```
+/// use const_map::const_map;
+///
+/// struct Primes;
+///
+/// impl Primes {
+///     const_map! {
+///         LINEAR, nth_linear() [linear], (u8 => u32) { 0 => 2, 1 => 3, 2 => 5 };
+///         BINARY, nth_binary() [binary], (u8 => u32) { 0 => 2, 1 => 3, 2 => 5 };
+///         HASHED, nth_hashed() [hashed], (u8 => u32) { 0 => 2, 1 => 3, 2 => 5 };
+///         JUMP, nth_jump() [jump], (u8 => u32) { 0 => 2, 1 => 3, 2 => 5 };
+///         MATCH, nth_match() [match], (u8 => u32) { 0 => 2, 1 => 3, 2 => 5 };
+///     }
+/// }
+///
+/// assert_eq!(Primes::nth_linear(2), Primes::nth_match(2));
+/// assert_eq!(Primes::nth_binary(1), Primes::nth_jump(1));
+/// assert_eq!(Primes::nth_hashed(3), None);
+/// ```
+///
+/// Whichever algorithm is used, the map holds the entries in the order they're written, leaving
+/// out any whose `#[cfg]` is false, and this is guaranteed not to change. Binary search and `jump`
+/// need the keys to be written in order rather than sorting them, and `hashed` maps keep their hash
+/// table apart from the map, so a map can be relied on to list entries by priority, for example.
+///
+/// If the name of the map is preceded by `split`, as in `sorted split NAME`, the map has type
+/// [`Split<KeyType, ValueType, N>`](Split) instead, which holds the keys and the values in two
+/// separate arrays. This avoids padding between keys and values when one is much smaller than the
+/// other, and the lookup function only has to look through the keys. It only works with linear
+/// search and `sorted` maps, and the options which can be used with it are the ones which look up
+/// a key (`get_ref`, `get_key_value`, `canonicalize`, `try_get`, `from_str`, `contains_key`,
+/// `is_known`, `contains_all`, `contains_any`, `position`, `or`, `or_else`, `fallback`, `flatten`,
+/// `normalize`, `get_or`, `expect` and `must`), along with `keys`, `values`, `values_sorted`, `len`
+/// and `is_empty`. The index from `position` can be used with the `values` array directly.
+///
+/// ```
+/// use const_map::const_map;
+///
+/// struct Blocks;
+///
+/// impl Blocks {
+///     const_map!(sorted split MAP, get(), (u8 => [u64; 4]) {
+///         1 => [1, 0, 0, 0],
+///         2 => [0, 1, 0, 0],
+///     }, position: position());
+/// }
+///
+/// assert_eq!(Blocks::get(2), Some([0, 1, 0, 0]));
+/// assert_eq!(Blocks::MAP.keys, [1, 2]);
+/// assert_eq!(Blocks::MAP.values[Blocks::position(1).unwrap()], [1, 0, 0, 0]);
+/// ```
+///
+/// If the name of the map is preceded by `interned`, as in `sorted interned NAME`, the map has type
+/// [`Interned<KeyType, ValueType, N, D>`](Interned) instead, which stores each of the `D` distinct
+/// values once, and the index of its value with each key. This makes the map smaller when many
+/// keys have the same value, especially a big one. It works with any lookup algorithm, but the
+/// value type has to support `==` in a const context, and it can't use the generic parameters of
+/// the `impl` block. The options which can be used with it are the same as for `split` maps.
+///
+/// ```
+/// use const_map::const_map;
+///
+/// struct Extensions;
+///
+/// impl Extensions {
+///     const_map!(hashed interned MAP, mime_type(), (&'static str => &'static str) {
+///         "htm" => "text/html",
+///         "html" => "text/html",
+///         "jpeg" => "image/jpeg",
+///         "jpg" => "image/jpeg",
+///     });
+/// }
+///
+/// assert_eq!(Extensions::mime_type("jpg"), Some("image/jpeg"));
+/// assert_eq!(Extensions::MAP.values, ["text/html", "image/jpeg"]);
+/// assert_eq!(Extensions::MAP.entries[1], ("html", 0));
+/// ```
+///
+/// If the key type is written as `&'static str`, keys are compared byte by byte, since `==` can't
+/// be used on strings in a const context, and the generated functions take keys as `&str`. The
+/// same applies to values for the reverse lookup function. Byte strings are supported in the same
+/// way, by writing the type as `&'static [u8]`.
+///
+/// If the key type is written as `ci &'static str` instead, keys are compared ignoring ASCII case,
+/// so that `"Content-Type"` finds the same entry as `"content-type"`. Keys which only differ in
+/// case count as duplicates, and the keys of `sorted` maps must be in order ignoring case.
+///
+/// Likewise, if the key type is written as `enum KeyType`, it can be an enum without any fields,
+/// which must be `Copy`. Keys are compared by their discriminants, so the lookup works for
+/// `sorted` and `hashed` maps too, in which case the keys must be ordered by discriminant. The
+/// same applies to values written as `enum ValueType`. Since the map can't tell how many variants
+/// the enum has, use the `total` option with the number of variants to make sure every one of them
+/// is in the map:
+///
+/// ```
+/// use const_map::const_map;
+///
+/// #[derive(Clone, Copy)]
+/// enum Suit { Clubs, Diamonds, Hearts, Spades }
+///
+/// struct Suits;
+///
+/// impl Suits {
+///     const_map!(MAP, get(), (enum Suit => char) {
+///         Suit::Clubs => '♣',
+///         Suit::Diamonds => '♦',
+///         Suit::Hearts => '♥',
+///         Suit::Spades => '♠',
+///     }, total(4): symbol());
+/// }
+///
+/// assert_eq!(Suits::symbol(Suit::Hearts), '♥');
+/// ```
+///
+/// To print enum keys as their values, [`impl_display!`] implements `Display` for the key type.
+///
+/// Tuples of two to four elements, such as `(u8, u8)`, can be used as keys if each element can be
+/// compared with `==` and `<` in a const context. They are compared one element at a time, the
+/// same as with `PartialEq` and `PartialOrd`. This only works with linear search and `sorted` maps.
+///
+/// With the `const-trait` feature, which needs a nightly compiler, keys which don't need a special
+/// comparison are compared with [`PartialEq::eq`] instead of `==`, so any key type with a const
+/// `PartialEq` implementation (`impl const PartialEq`) works, not only the built-in ones. The same
+/// applies to values, and to the elements of tuple keys. The crate using the maps has to enable
+/// `#![feature(const_trait_impl)]` as well. Ordering still uses `<`, so the keys of `sorted` maps
+/// need it as before, but duplicate keys are found by comparing every pair of keys, which takes
+/// longer for big maps.
+///
+/// Keys of type `f32` or `f64` are compared by their total order, as with `f64::total_cmp`, rather
+/// than with `==`. This means a `NaN` key can be found like any other, but `-0.0` and `0.0` are
+/// different keys. The same applies to values of those types. Float keys can't be used for `jump`
+/// or `match` maps.
+///
+/// If the key type is written as `ref KeyType`, the generated functions take keys by reference, as
+/// `&KeyType`, so that big keys don't need to be copied and keys don't need to be `Copy`. This only
+/// works with linear search and `sorted` maps. Keys which don't support `==` in a const context,
+/// such as arrays, can be used with a custom comparison function:
+///
+/// ```
+/// use const_map::const_map;
+///
+/// const fn same_bytes(a: &[u8; 4], b: &[u8; 4]) -> bool {
+///     a[0] == b[0] && a[1] == b[1] && a[2] == b[2] && a[3] == b[3]
+/// }
+///
+/// struct Magic;
+///
+/// impl Magic {
+///     const_map!(MAP, format() using same_bytes, (ref [u8; 4] => &'static str) {
+///         *b"\x7fELF" => "ELF",
+///         *b"%PDF" => "PDF",
+///     });
+/// }
+///
+/// assert_eq!(Magic::format(b"%PDF"), Some("PDF"));
+/// ```
+///
+/// A custom comparison function given with `using` can only be used with the default linear
+/// search, since the others need to do more than check keys for equality. It is also used to check
+/// the keys are unique, and while the lookup functions take keys of type `KeyType` as usual, they
+/// can be found by any key which the function says is equal:
+///
+/// ```
+/// use const_map::const_map;
+///
+/// #[derive(Clone, Copy)]
+/// struct Version { major: u32, minor: u32 }
+///
+/// const fn same_major(a: &Version, b: &Version) -> bool {
+///     a.major == b.major
+/// }
+///
+/// struct Releases;
+///
+/// impl Releases {
+///     const_map!(MAP, codename() using same_major, (Version => &'static str) {
+///         Version { major: 1, minor: 0 } => "Ash",
+///         Version { major: 2, minor: 0 } => "Birch",
+///     });
+/// }
+///
+/// assert_eq!(Releases::codename(Version { major: 2, minor: 5 }), Some("Birch"));
+/// ```
+///
+/// If the key type is written as `range KeyType`, each key is an inclusive range such as
+/// `'a'..='z'`, stored as `RangeInclusive<KeyType>`, and the generated functions take a single
+/// `KeyType`, finding the entry whose range contains it. Ranges which overlap are a compile error,
+/// and for `sorted` maps, each range must end before the next one starts. This only works with
+/// linear search and `sorted` maps, and since ranges aren't `Copy`, options which return keys can't
+/// be used.
+///
+/// ```
+/// use const_map::const_map;
+///
+/// struct Grades;
+///
+/// impl Grades {
+///     const_map!(sorted MAP, grade(), (range u8 => char) {
+///         0..=49 => 'F',
+///         50..=69 => 'C',
+///         70..=84 => 'B',
+///         85..=100 => 'A',
+///     });
+/// }
+///
+/// assert_eq!(Grades::grade(72), Some('B'));
+/// assert_eq!(Grades::grade(101), None);
+/// ```
+///
+/// If the map is declared as `multi NAME`, keys don't need to be unique, so a key can have several
+/// values. The lookup function finds the first of them, and the `get_all` option can be used to
+/// find all of them. Only linear search can be used for these maps. `allow_duplicates NAME` means
+/// the same thing, for maps where a duplicate key is meant to be shadowed by the first one.
+///
+/// Any map can have a reverse lookup function, even if several keys have the same value, in which
+/// case it finds the first of them:
+///
+/// ```
+/// use const_map::const_map;
+///
+/// struct Sizes;
+///
+/// impl Sizes {
+///     const_map!(MAP, size(), smallest(), (&'static str => u8) {
+///         "tiny" => 0,
+///         "small" => 1,
+///         "little" => 1,
+///         "big" => 2,
+///     });
+/// }
+///
+/// assert_eq!(Sizes::size("little"), Some(1));
+/// assert_eq!(Sizes::smallest(1), Some("small"));
+/// ```
+///
+/// If the map is declared as `bijective NAME`, there's exactly one key for each value, which means
+/// the reverse lookup function can be relied on to give the only key with the value, rather than
+/// just the first one:
+///
+/// ```
+/// use const_map::const_map;
+///
+/// struct Codes;
+///
+/// impl Codes {
+///     const_map!(bijective MAP, code(), letter(), (char => u8) {
+///         'a' => 1,
+///         'b' => 2,
+///         'c' => 3,
+///     });
+/// }
+///
+/// assert_eq!(Codes::code('b'), Some(2));
+/// assert_eq!(Codes::letter(3), Some('c'));
+/// ```
+///
+/// The `impl` block can have generic parameters, which the value type can use, such as a lifetime
+/// in `&'a str`. The generated functions get the parameters and any `where` clauses from the `impl`
+/// block, so there's no need to give them separately. The key type can't use them, though, and
+/// neither can the values of a `bijective` map or one with the `unique_values` option, since those
+/// are checked in a const context where generic parameters aren't available. The exception is
+/// string and byte string keys, which can be written as `&'a str` or `&'a [u8]`: since keys are
+/// constants, they're stored as `&'static str` or `&'static [u8]` anyway, and the lookup functions
+/// take them with any lifetime.
+///
+/// ```
+/// use const_map::const_map;
+///
+/// struct Labels<'a>(&'a str);
+///
+/// impl<'a> Labels<'a> {
+///     const_map!(MAP, get(), (&'a str => Option<&'a str>) {
+///         "yes" => Some("y"),
+///         "no" => None,
+///     });
+///
+///     fn short(&self) -> Option<&'a str> {
+///         Self::get(self.0).flatten()
+///     }
+/// }
+///
+/// let input = String::from("yes");
+/// assert_eq!(Labels(&input).short(), Some("y"));
+/// ```
+///
+/// The lookup function can also have generic parameters of its own and a `where` clause, as in
+/// `get<'b>() where T: Copy`, which all of the generated functions get. The parameters are written
+/// without bounds, which go in the `where` clause instead, and it comes last, after anything else
+/// given with the lookup function. This is how bounds which the `impl` block doesn't have can be
+/// added, such as the `Copy` needed to return values which use its type parameters.
+///
+/// ```
+/// use const_map::const_map;
+///
+/// struct Slot<T>(T);
+///
+/// impl<T> Slot<T> {
+///     const_map!(MAP, get() where T: Copy, (u8 => Option<T>) {
+///         0 => None,
+///     }, contains_key: has());
+/// }
+///
+/// assert_eq!(Slot::<u32>::get(0), Some(None));
+/// assert!(!Slot::<u32>::has(1));
+/// ```
+///
+/// Values can be function pointers, which makes a map a dispatch table:
+///
+/// ```
+/// use const_map::const_map;
+///
+/// fn push_one(stack: &mut Vec<i32>) {
+///     stack.push(1);
+/// }
+///
+/// fn add(stack: &mut Vec<i32>) {
+///     let (a, b) = (stack.pop().unwrap(), stack.pop().unwrap());
+///     stack.push(a + b);
+/// }
+///
+/// struct Ops;
+///
+/// impl Ops {
+///     const_map!(jump MAP, get(), (u8 => fn(&mut Vec<i32>)) {
+///         0 => push_one,
+///         1 => add,
+///     });
+/// }
+///
+/// let mut stack = vec![];
+/// for op in [0, 0, 1, 0, 1] {
+///     Ops::get(op).expect("unknown op")(&mut stack);
+/// }
+/// assert_eq!(stack, [3]);
+/// ```
+///
+/// Values can refer to other maps, or to types which have maps of their own, so tables can be
+/// nested, and the lookups composed in a const context with `match`:
+///
+/// ```
+/// use const_map::const_map;
+///
+/// #[derive(Clone, Copy)]
+/// enum Shape { Square, Triangle }
+///
+/// impl Shape {
+///     const_map! {
+///         SQUARE, square(), (&'static str => u32) { "sides" => 4, "corners" => 4 };
+///         TRIANGLE, triangle(), (&'static str => u32) { "sides" => 3, "corners" => 3 };
+///     }
+///
+///     const fn count(self, what: &str) -> Option<u32> {
+///         match self {
+///             Shape::Square => Self::square(what),
+///             Shape::Triangle => Self::triangle(what),
+///         }
+///     }
+/// }
+///
+/// struct Shapes;
+///
+/// impl Shapes {
+///     const_map!(MAP, get(), (&'static str => Shape) {
+///         "square" => Shape::Square,
+///         "triangle" => Shape::Triangle,
+///     });
+/// }
+///
+/// const SIDES: Option<u32> = match Shapes::get("triangle") {
+///     Some(shape) => shape.count("sides"),
+///     None => None,
+/// };
+/// assert_eq!(SIDES, Some(3));
+/// ```
+///
+/// The kinds of extra items which can be generated are as follows. Unless noted otherwise, each
+/// of them can only be a function.
+///
+/// * `get_ref`: `const fn(k: KeyType) -> Option<&'static ValueType>`, which is like a lookup
+///   function written as `&lookup()`.
+/// * `get_key_value`: `const fn(k: KeyType) -> Option<(KeyType, ValueType)>`, which returns the
+///   entry for the key. This is useful when keys which aren't the same can be equal, such as with
+///   a custom comparison function, to find out what the key in the map is.
+/// * `try_get`: `const fn(k: KeyType) -> Result<ValueType, NotFound<KeyType>>`, which is like the
+///   lookup function but returns a [`NotFound`] error holding the key if it isn't in the map, which
+///   is convenient with the `?` operator.
+/// * `from_str`: `const fn(s: &str) -> Option<ValueType>`, which is the same as the lookup function
+///   for maps with string keys, for a parsing function with a clear name, such as
+///   `color_from_str()`. Unlike [`impl_from_str!`], it can be used for any number of maps with the
+///   same value type.
+/// * `contains_key`: `const fn(k: KeyType) -> bool`, which returns whether the key is in the map.
+/// * `is_known`: the same as `contains_key`, for checking that a key is valid before using it.
+/// * `canonicalize`: `const fn(k: KeyType) -> Option<KeyType>`, which returns the key in the map
+///   which is equal to `k`. Like `get_key_value`, this is useful when keys which aren't the same
+///   can be equal, such as with a custom comparison function, to turn them into the one in the map.
+/// * `contains_all`: `const fn(keys: &[KeyType]) -> bool`, which returns whether all the keys are
+///   in the map, such as for checking at compile time that a map has every mandatory entry.
+/// * `contains_any`: `const fn(keys: &[KeyType]) -> bool`, which returns whether any of the keys is
+///   in the map.
+/// * `many`: `const fn<const M: usize>(keys: [KeyType; M]) -> [Option<ValueType>; M]`, which looks
+///   up each of the keys, for looking up a batch of keys in one go.
+/// * `position`: `const fn(k: KeyType) -> Option<usize>`, which returns the index of the key's
+///   entry in the map. This is useful for looking up the key in other arrays in the same order.
+/// * `get_all`: `const fn(k: KeyType, out: &mut [ValueType]) -> usize`, which copies the values
+///   for the key into `out`, in order, and returns how many there are. If `out` is too short, the
+///   values which don't fit are left out, but they are still counted. This is mostly useful for
+///   `multi` maps.
+/// * `contains_value`: `const fn(v: ValueType) -> bool`, which returns whether any key has the
+///   value.
+/// * `keys_for`: `const fn(v: ValueType, out: &mut [KeyType]) -> usize`, which copies the keys with
+///   the value into `out`, in order, and returns how many there are. Like with `get_all`, the keys
+///   which don't fit are still counted.
+/// * `reverse`: `const fn(v: ValueType) -> Option<KeyType>`, which is like a reverse lookup
+///   function.
+/// * `reverse_unique`: the same as `reverse`, but it is a compile error if any two keys have the
+///   same value, so the function is sure to return the only key with the value. This is what the
+///   reverse lookup function of a `bijective` map is.
+/// * `reverse_sorted`: the same as `reverse_unique`, but the values must be in ascending order,
+///   which is a compile error otherwise, and the function does a binary search. This requires the
+///   value type to support `<` in a const context, the same as the key type of `sorted` maps. This
+///   is what the reverse lookup function written as `sorted reverse_lookup()` is.
+/// * `reverse_using(eq_fn)`: the same as `reverse`, but values are compared with `eq_fn`, a path
+///   to a `const fn(&ValueType, &ValueType) -> bool`, instead of `==`. This works for value types
+///   which can't be compared in a const context otherwise, and is what the reverse lookup function
+///   written as `reverse_lookup() using eq_fn` is.
+/// * `or(default)`: `const fn(k: KeyType) -> ValueType`, which returns the value for the key, or
+///   `default` if the key isn't in the map. This is what the lookup function written as
+///   `lookup() or default` is.
+/// * `flatten`: `const fn(k: KeyType) -> ValueType`, for maps whose values are `Option`s, which
+///   returns the value for the key, or `None` if the key isn't in the map, rather than wrapping the
+///   value in another `Option`. The lookup function can still tell a key whose value is `None`
+///   apart from a key which isn't in the map, since it returns `Some(None)` for the first.
+/// * `or_else(fallback)`: `const fn(k: KeyType) -> ValueType`, which returns the value for the key,
+///   or if the key isn't in the map, the result of calling `fallback` with it. `fallback` is a path
+///   to a `const fn(KeyType) -> ValueType`, such as `Self::unknown`.
+/// * `fallback(other_lookup)`: `const fn(k: KeyType) -> Option<ValueType>`, which is like the
+///   lookup function, but if the key isn't in the map, returns the result of calling
+///   `other_lookup` with it instead. `other_lookup` is a path to a
+///   `const fn(KeyType) -> Option<ValueType>`, such as the lookup function of another map, so that
+///   a map can override some of the entries of a base map. This is what the lookup function
+///   written as `lookup() fallback other_lookup` is.
+/// * `normalize(norm_fn)`: `const fn(k: KeyType) -> Option<ValueType>`, which is like the lookup
+///   function, but first replaces the key with the result of calling `norm_fn` with it. `norm_fn`
+///   is a path to a `const fn(KeyType) -> KeyType`, which can trim strings or turn aliases into
+///   the keys of the map, for example, so that they don't need entries of their own. This is what
+///   the lookup function written as `lookup() normalize norm_fn` is.
+/// * `get_or`: `const fn(k: KeyType, default: ValueType) -> ValueType`, which returns the value
+///   for the key, or `default` if the key isn't in the map.
+/// * `expect`: `const fn(k: KeyType, msg: &'static str) -> ValueType`, which returns the value for
+///   the key, or panics with the given message if the key isn't in the map. When used in a const
+///   context, this makes a missing key a compile error.
+/// * `must`: `const fn(k: KeyType) -> ValueType`, which is like `expect`, but panics with a message
+///   naming the map. This is handy for looking up a const generic parameter in an associated
+///   constant, where a missing key is a compile error:
+///
+///   ```
+///   use const_map::const_map;
+///
+///   struct Fruits;
+///
+///   impl Fruits {
+///       const_map!(MAP, get(), (char => &'static str) {
+///           'a' => "apple",
+///           'b' => "banana",
+///       }, must: must());
+///   }
+///
+///   struct Fruit<const C: char>;
+///
+///   impl<const C: char> Fruit<C> {
+///       const NAME: &'static str = Fruits::must(C);
+///   }
+///
+///   assert_eq!(Fruit::<'b'>::NAME, "banana");
+///   ```
+/// * `keys`: all the keys of the map, in order, either as `const fn() -> [KeyType; N]` or as a
+///   constant of type `[KeyType; N]`.
+/// * `values`: all the values of the map, in order, either as `const fn() -> [ValueType; N]` or as
+///   a constant of type `[ValueType; N]`.
+/// * `values_sorted`: the same as `values`, but with the values sorted in ascending order, which
+///   is done at compile time. This requires the value type to support `<` in a const context, the
+///   same as the key type of `sorted` maps.
+/// * `inverse`: the entries of the map with each key and value swapped, in order, either as
+///   `const fn() -> [(ValueType, KeyType); N]` or as a constant of that type. This is useful for
+///   iterating over the map by value, or as another map.
+/// * `inverse_sorted`: the same as `inverse`, but sorted by value at compile time, which requires
+///   the value type to support `<` in a const context. Keys with the same value stay in the same
+///   order. Since the values are in order, the result can be searched with
+///   [`slice::binary_search_by`], for example.
+/// * `first` and `last`: `const fn() -> Option<(KeyType, ValueType)>`, which return the first or
+///   last entry of the map, in the order they're written, or `None` if the map is empty.
+/// * `min_key` and `max_key`: `const fn() -> KeyType`, which return the smallest or largest key in
+///   the map. This requires the key type to support `<` in a const context, the same as for
+///   `sorted` maps, and it is a compile error if the map is empty.
+/// * `floor`: `const fn(k: KeyType) -> Option<ValueType>`, which returns the value for the largest
+///   key in the map which isn't greater than `k`, or `None` if every key is greater. This is useful
+///   for maps of thresholds, and like `min_key`, requires the key type to support `<` in a const
+///   context. The keys don't need to be sorted.
+/// * `entries`: the entries of the map as a slice, in order, without depending on the map's name
+///   or number of entries, either as `const fn() -> &'static [(KeyType, ValueType)]` or as a
+///   constant of type `&'static [(KeyType, ValueType)]`.
+/// * `entries_in_order`: the same as `entries`, with a name which makes it clear that the code
+///   using it relies on the entries being in the order they're written.
+/// * `all(pred)` and `any(pred)`: `const fn() -> bool`, which return whether the predicate is true
+///   for every entry of the map, or for any of them. The predicate is the path to a
+///   `const fn(KeyType, ValueType) -> bool`, such as `Self::is_valid`, so the result can be
+///   checked at compile time with `const _: () = assert!(...);`. Like options which return keys,
+///   these can't be used with range keys.
+/// * `count(pred)`: `const fn() -> usize`, which returns how many entries of the map the predicate
+///   is true for, with the predicate given the same way as for `all` and `any`. This can check
+///   rules such as exactly one entry being the default one at compile time.
+/// * `iter`: `fn() -> core::slice::Iter<'static, (KeyType, ValueType)>`, which iterates over
+///   references to the entries of the map, in order. Unlike the others, this isn't a `const fn`.
+/// * `total(n)`: `const fn(k: KeyType) -> ValueType`, for maps which contain every possible value
+///   of the key type, of which there are `n`. It is a compile error if the map doesn't have `n`
+///   entries. For `bool`, `char` and integer keys, it's also a compile error if `n` isn't the
+///   number of values of the key type, so since keys are unique, the function can't fail. For
+///   other keys, such as enums, `n` can't be checked, and the function panics if it's given a key
+///   which isn't in the map.
+/// * `hashmap`: `fn() -> std::collections::HashMap<KeyType, ValueType>`, which returns a new
+///   `HashMap` with the entries of the map. This requires the key type to implement `Eq` and
+///   `Hash`, both types to implement `Clone`, and the `std` feature, which is enabled by default.
+///   Like `iter`, this isn't a `const fn`.
+/// * `btreemap`: `fn() -> alloc::collections::BTreeMap<KeyType, ValueType>`, which is like
+///   `hashmap`, but returns a `BTreeMap`, requiring the key type to implement `Ord` instead. This
+///   needs the `alloc` feature, which is enabled by the `std` feature.
+/// * `hashbrown`: `fn() -> hashbrown::HashMap<KeyType, ValueType>`, which is like `hashmap`, but
+///   returns a [hashbrown](https://docs.rs/hashbrown) `HashMap`, which works without `std`. This
+///   needs the `hashbrown` feature.
+/// * `serialize`: `const fn() -> Serializable<KeyType, ValueType>`, which returns the entries of
+///   the map wrapped in a type that serde can serialize as a map from keys to values, if both types
+///   implement `Serialize`. This needs the `serde` feature. If a key appears more than once in a
+///   `multi` map, it is serialized more than once too.
+/// * `to_json`: `fn() -> String`, which returns the entries of the map as a JSON array of
+///   `[key, value]` arrays, in order, if both types implement `Serialize`. This is handy for
+///   logging what a map holds, or for snapshot tests. It needs the `json` feature, which enables
+///   the `serde` and `alloc` features too, and it panics if serializing a key or a value fails.
+/// * `doc_hidden`: this is written on its own, without a name, and it puts `#[doc(hidden)]` on the
+///   items generated by the options which come after it, so that a long list of helper functions
+///   doesn't clutter the documentation. The map and the lookup functions stay documented, as do
+///   the items of any options written before `doc_hidden`.
+/// * `unique_values`: this is written on its own, without a name, and instead of generating
+///   anything, it makes it a compile error if any two keys have the same value, in the same way as
+///   for a `bijective` map.
+/// * `require(key, ...)`: like `unique_values`, this is written on its own, and it makes it a
+///   compile error if a lookup for any of the given keys wouldn't find it, so that entries which
+///   are needed can't be removed by mistake. The keys are written the way they would be passed to
+///   the lookup function.
+/// * `validate(validator)`: like `unique_values`, this is written on its own, and it makes it a
+///   compile error if `validator`, a path to a `const fn(&[(KeyType, ValueType)]) -> bool`, returns
+///   `false` for the entries of the map. This can check any rule the entries must follow, such as
+///   values being within some range. It can be given more than once.
+/// * `len`: the number of entries in the map, either as `const fn() -> usize` or as a constant of
+///   type `usize`.
+/// * `is_empty`: whether the map has no entries, either as `const fn() -> bool` or as a constant
+///   of type `bool`. Maps can be empty, in which case lookups never find anything.
+/// * `array_type`: a type alias for the type of the map, `[(KeyType, ValueType); N]`, written as
+///   `array_type: Name`. Since associated types can't be defined in an `impl` block, this only
+///   works with [`free_const_map!`]; with `const_map!`, use `[(KeyType, ValueType); Self::LEN]`
+///   with the `len` option instead.
 #[macro_export]
 macro_rules! const_map {
-    ($name:ident, $lookup:ident(), ($kty:ty => $vty:ty) { $($k:expr => $v:expr),* $(,)? }) => {
-        pub const $name: [($kty, $vty); $crate::count!($(($k, $v))*)] = [$(($k, $v)),*];
+    ($($rest:tt)*) => {
+        $crate::const_map_impl!(@head [Self] [] [unique] $($rest)*);
+    };
+}
 
-        const fn $lookup(key: $kty) -> Option<$vty> {
-            #[inline]
-            const fn find(pairs: &[($kty, $vty)], key: $kty, n: usize) -> Option<$vty> {
-                if n >= pairs.len() {
-                    return None;
-                }
-                match pairs[n] {
-                    (k, v) if k == key => Some(v),
-                    _ => find(pairs, key, n + 1),
+/// Like [`const_map!`], but for use at module level instead of in an `impl` block.
+///
+/// The map becomes a constant and the lookup function (and any other generated functions) become
+/// free functions, so there's no need for a struct to hold them.
+///
+/// ```
+/// use const_map::free_const_map;
+///
+/// free_const_map!(FRUITS, fruit(), (char => &'static str) {
+///     'a' => "apple",
+///     'b' => "banana",
+/// }, contains_key: is_fruit());
+///
+/// assert_eq!(fruit('a'), Some("apple"));
+/// assert!(!is_fruit('c'));
+/// assert_eq!(FRUITS.len(), 2);
+/// ```
+///
+/// The name of the map can be preceded by `static`, as in `sorted static NAME`, in which case the
+/// map is a `static` instead of a constant. A constant is copied wherever it's used, which can
+/// make a big map take up more space than it needs to, but there's only ever one copy of a
+/// static. The generated functions are still `const fn`s. Associated statics aren't allowed, so
+/// this can't be done with [`const_map!`].
+///
+/// ```
+/// use const_map::free_const_map;
+///
+/// free_const_map!(sorted static SQUARES, square_root(), (u32 => u32) {
+///     1 => 1,
+///     4 => 2,
+///     9 => 3,
+/// });
+///
+/// const ROOT: Option<u32> = square_root(9);
+/// assert_eq!(ROOT, Some(3));
+/// assert_eq!(SQUARES.len(), 3);
+/// ```
+///
+/// The name of the map can also be preceded by `try_from`, which implements `TryFrom<KeyType>`
+/// for the value type using the map. The error of the conversion is a unit struct generated for
+/// the map, which says which map the key wasn't found in. This can't be done with [`const_map!`];
+/// use [`impl_try_from!`] there instead.
+///
+/// ```
+/// use const_map::free_const_map;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Color { Red, Green }
+///
+/// free_const_map!(try_from COLORS, color(), (u8 => Color) {
+///     1 => Color::Red,
+///     2 => Color::Green,
+/// });
+///
+/// assert_eq!(Color::try_from(2), Ok(Color::Green));
+/// let err = Color::try_from(3).unwrap_err();
+/// assert_eq!(err.to_string(), "key not found in const_map COLORS");
+/// ```
+#[macro_export]
+macro_rules! free_const_map {
+    ($($rest:tt)*) => {
+        $crate::const_map_impl!(@head [] [] [unique] $($rest)*);
+    };
+}
+
+/// Implement `TryFrom<KeyType>` for the value type of a const map, using its lookup function.
+///
+/// Because [`const_map!`] is used inside an `impl` block, where trait implementations can't go,
+/// this is a separate macro which must be used at module level instead. The error type of the
+/// conversion is [`NotFound`]. For a [`free_const_map!`], the `try_from` keyword does the same
+/// thing without naming the lookup function.
+///
+/// ```
+/// use const_map::{const_map, impl_try_from, NotFound};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Color { Red, Green }
+///
+/// struct Colors;
+///
+/// impl Colors {
+///     const_map!(MAP, get(), (u8 => Color) {
+///         1 => Color::Red,
+///         2 => Color::Green,
+///     });
+/// }
+///
+/// impl_try_from!(Colors::get, (u8 => Color));
+///
+/// assert_eq!(Color::try_from(2), Ok(Color::Green));
+/// assert_eq!(Color::try_from(3), Err(NotFound(3)));
+/// ```
+#[macro_export]
+macro_rules! impl_try_from {
+    ($lookup:path, ($kty:ty => $vty:ty)) => {
+        impl ::core::convert::TryFrom<$kty> for $vty {
+            type Error = $crate::NotFound<$kty>;
+
+            fn try_from(key: $kty) -> ::core::result::Result<Self, Self::Error> {
+                match $lookup(key) {
+                    ::core::option::Option::Some(value) => ::core::result::Result::Ok(value),
+                    ::core::option::Option::None => ::core::result::Result::Err($crate::NotFound(key)),
                 }
             }
-            find(&Self::$name, key, 0)
         }
     };
 }
 
-#[doc(hidden)]
+/// Implement `From<KeyType>` for the value type of a const map, using a function generated by the
+/// map's `total` option.
+///
+/// Like [`impl_try_from!`], this must be used at module level.
+///
+/// ```
+/// use const_map::{const_map, impl_from};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Answer { Yes, No }
+///
+/// struct Answers;
+///
+/// impl Answers {
+///     const_map!(MAP, get(), (bool => Answer) {
+///         true => Answer::Yes,
+///         false => Answer::No,
+///     }, total(2): get_total());
+/// }
+///
+/// impl_from!(Answers::get_total, (bool => Answer));
+///
+/// assert_eq!(Answer::from(false), Answer::No);
+/// ```
 #[macro_export]
-macro_rules! count {
-    () => (0usize);
-    ($x:tt $($xs:tt)*) => (1usize + $crate::count!($($xs)*));
+macro_rules! impl_from {
+    ($lookup:path, ($kty:ty => $vty:ty)) => {
+        impl ::core::convert::From<$kty> for $vty {
+            fn from(key: $kty) -> Self {
+                $lookup(key)
+            }
+        }
+    };
 }
 
-#[cfg(test)]
-mod test {
-    struct S1;
+/// Implement `FromStr` for the value type of a const map with string keys, using its lookup
+/// function, so that strings can be parsed with `str::parse`. Keys can be compared ignoring ASCII
+/// case too, by declaring the key type as `ci &'static str`.
+///
+/// Parsing a string which isn't a key fails with a [`ParseError`]. Like [`impl_try_from!`], this
+/// must be used at module level.
+///
+/// ```
+/// use const_map::{const_map, impl_from_str, ParseError};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Color { Red, Green }
+///
+/// struct Colors;
+///
+/// impl Colors {
+///     const_map!(MAP, get(), (&'static str => Color) {
+///         "red" => Color::Red,
+///         "green" => Color::Green,
+///     });
+/// }
+///
+/// impl_from_str!(Colors::get, Color);
+///
+/// assert_eq!("red".parse::<Color>(), Ok(Color::Red));
+/// assert_eq!("blue".parse::<Color>(), Err(ParseError));
+/// ```
+#[macro_export]
+macro_rules! impl_from_str {
+    ($lookup:path, $vty:ty) => {
+        impl ::core::str::FromStr for $vty {
+            type Err = $crate::ParseError;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                match $lookup(s) {
+                    ::core::option::Option::Some(value) => ::core::result::Result::Ok(value),
+                    ::core::option::Option::None => ::core::result::Result::Err($crate::ParseError),
+                }
+            }
+        }
+    };
+}
+
+/// Implement `Display` for the key type of a const map, using its lookup function, so that keys
+/// are formatted as their value, which can be any type that implements `Display`, such as a
+/// `&'static str`. The key type must be `Copy`.
+///
+/// Formatting a key which isn't in the map returns an error, which makes `format!` and
+/// `to_string` panic, so this is best used with maps that have an entry for every key, which the
+/// `total` option can check. Like [`impl_try_from!`], this must be used at module level.
+///
+/// ```
+/// use const_map::{const_map, impl_display};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Color { Red, Green }
+///
+/// struct Colors;
+///
+/// impl Colors {
+///     const_map!(MAP, name(), (enum Color => &'static str) {
+///         Color::Red => "red",
+///         Color::Green => "green",
+///     }, total(2): name_total());
+/// }
+///
+/// impl_display!(Colors::name, Color);
+///
+/// assert_eq!(Color::Green.to_string(), "green");
+/// assert_eq!(format!("{:>5}", Color::Red), "  red");
+/// ```
+#[macro_export]
+macro_rules! impl_display {
+    ($lookup:path, $kty:ty) => {
+        impl ::core::fmt::Display for $kty {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match $lookup(*self) {
+                    ::core::option::Option::Some(value) => ::core::fmt::Display::fmt(&value, f),
+                    ::core::option::Option::None => ::core::result::Result::Err(::core::fmt::Error),
+                }
+            }
+        }
+    };
+}
+
+/// Define a unit struct which can be indexed by the keys of a const map, using a lookup function
+/// which returns a reference: either the main lookup function written as `&lookup()`, or one
+/// generated by the `get_ref` option.
+///
+/// Indexing with a key which isn't in the map panics. Like [`impl_try_from!`], this must be used
+/// at module level.
+///
+/// ```
+/// use const_map::{const_map, impl_index};
+///
+/// struct Fruits;
+///
+/// impl Fruits {
+///     const_map!(MAP, get(), (char => &'static str) {
+///         'a' => "apple",
+///         'b' => "banana",
+///     }, get_ref: get_ref());
+/// }
+///
+/// impl_index!(pub struct FRUITS, Fruits::get_ref, (char => &'static str));
+///
+/// assert_eq!(FRUITS['b'], "banana");
+/// ```
+#[macro_export]
+macro_rules! impl_index {
+    ($(#[$attr:meta])* $vis:vis struct $wrapper:ident, $lookup:path, ($kty:ty => $vty:ty)) => {
+        $(#[$attr])*
+        #[allow(non_camel_case_types)]
+        #[derive(Debug, Clone, Copy)]
+        $vis struct $wrapper;
+
+        impl ::core::ops::Index<$kty> for $wrapper {
+            type Output = $vty;
+
+            fn index(&self, key: $kty) -> &$vty {
+                match $lookup(key) {
+                    ::core::option::Option::Some(value) => value,
+                    ::core::option::Option::None => panic!("key not found"),
+                }
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! const_map_impl {
+    // Work out which keywords come before the name of the map, and from them, how to look up keys
+    // and how to check them at compile time.
+    (@head $scope:tt [] [unique]) => {};
+    // `try_from` implements `TryFrom` for the value type, which can't be done in an `impl` block.
+    (@head [Self] $flags:tt $check:tt try_from $next:ident $($rest:tt)*) => {
+        compile_error!("`try_from` can only be used with free_const_map!; use impl_try_from! instead");
+    };
+    (@head [] $flags:tt $check:tt try_from $next:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@head [try_from] $flags $check $next $($rest)*);
+    };
+    (@head $scope:tt [] [any] bijective $next:ident $($rest:tt)*) => {
+        compile_error!("a const_map can't be both multi and bijective");
+    };
+    (@head $scope:tt [] $check:tt bijective $next:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@head $scope [bijective] $check $next $($rest)*);
+    };
+    (@head $scope:tt $flags:tt [unique] ordered $next:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@head $scope $flags [sorted] $next $($rest)*);
+    };
+    (@head $scope:tt [] [unique] multi $next:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@head $scope [] [any] $next $($rest)*);
+    };
+    (@head $scope:tt [] [unique] allow_duplicates $next:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@head $scope [] [any] $next $($rest)*);
+    };
+    (@head $scope:tt $flags:tt [any] static $($rest:tt)*) => {
+        $crate::const_map_impl!(@name [linear any] $scope $flags static $($rest)*);
+    };
+    (@head $scope:tt $flags:tt [any] $algo:ident static $name:ident $([$len:expr])?, $($rest:tt)*) => {
+        compile_error!(concat!("multi const_map ", stringify!($name), " can only use linear search"));
+    };
+    (@head $scope:tt $flags:tt [any] $algo:ident $name:ident $([$len:expr])?, $($rest:tt)*) => {
+        compile_error!(concat!("multi const_map ", stringify!($name), " can only use linear search"));
+    };
+    (@head $scope:tt $flags:tt $check:tt sorted $next:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@name [binary sorted] $scope $flags $next $($rest)*);
+    };
+    (@head $scope:tt $flags:tt $check:tt jump $next:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@name [jump dense] $scope $flags $next $($rest)*);
+    };
+    (@head $scope:tt $flags:tt [unique] hashed $next:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@name [hash hashed] $scope $flags $next $($rest)*);
+    };
+    (@head $scope:tt $flags:tt [sorted] hashed $next:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@name [hash sorted_hashed] $scope $flags $next $($rest)*);
+    };
+    (@head $scope:tt $flags:tt [$check:ident] fnv $next:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@name [fnv $check] $scope $flags $next $($rest)*);
+    };
+    (@head $scope:tt $flags:tt [$check:ident] match $next:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@name [match $check] $scope $flags $next $($rest)*);
+    };
+    (@head $scope:tt $flags:tt [$check:ident] $($rest:tt)*) => {
+        $crate::const_map_impl!(@name [linear $check] $scope $flags $($rest)*);
+    };
+
+    // The name can be preceded by `static`, and followed by the declared number of entries. These
+    // are what the map is declared with, so they're kept together until then.
+    (@name $algo:tt [Self] $flags:tt static interned $name:ident $($rest:tt)*) => {
+        compile_error!(concat!("static const_map ", stringify!($name), " can only be defined with free_const_map!"));
+    };
+    (@name $algo:tt [Self] $flags:tt static split $name:ident $($rest:tt)*) => {
+        compile_error!(concat!("static const_map ", stringify!($name), " can only be defined with free_const_map!"));
+    };
+    (@name $algo:tt [Self] $flags:tt static $name:ident $($rest:tt)*) => {
+        compile_error!(concat!("static const_map ", stringify!($name), " can only be defined with free_const_map!"));
+    };
+    (@name $algo:tt $scope:tt $flags:tt static split $name:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@split $algo $scope $flags static $name $($rest)*);
+    };
+    (@name $algo:tt $scope:tt $flags:tt split $name:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@split $algo $scope $flags const $name $($rest)*);
+    };
+    (@name $algo:tt $scope:tt $flags:tt static interned $name:ident $([$len:expr])?, $($rest:tt)*) => {
+        $crate::const_map_impl!(@lookup $algo $scope $flags $name [static [$($len)?] [] interned [] [] []], $($rest)*);
+    };
+    (@name $algo:tt $scope:tt $flags:tt interned $name:ident $([$len:expr])?, $($rest:tt)*) => {
+        $crate::const_map_impl!(@lookup $algo $scope $flags $name [const [$($len)?] [] interned [] [] []], $($rest)*);
+    };
+    (@name $algo:tt $scope:tt $flags:tt static $name:ident $([$len:expr])?, $($rest:tt)*) => {
+        $crate::const_map_impl!(@lookup $algo $scope $flags $name [static [$($len)?] [] pairs [] [] []], $($rest)*);
+    };
+    (@name $algo:tt $scope:tt $flags:tt $name:ident $([$len:expr])?, $($rest:tt)*) => {
+        $crate::const_map_impl!(@lookup $algo $scope $flags $name [const [$($len)?] [] pairs [] [] []], $($rest)*);
+    };
+
+    // Split maps store their keys and values in separate arrays, which only linear search and
+    // binary search know how to look through.
+    (@split [linear $check:ident] $scope:tt $flags:tt $item:tt $name:ident $([$len:expr])?, $($rest:tt)*) => {
+        $crate::const_map_impl!(@lookup [linear $check] $scope $flags $name [$item [$($len)?] [] split [] [] []], $($rest)*);
+    };
+    (@split [binary $check:ident] $scope:tt $flags:tt $item:tt $name:ident $([$len:expr])?, $($rest:tt)*) => {
+        $crate::const_map_impl!(@lookup [binary $check] $scope $flags $name [$item [$($len)?] [] split [] [] []], $($rest)*);
+    };
+    (@split $algo:tt $scope:tt $flags:tt $item:tt $name:ident $($rest:tt)*) => {
+        compile_error!(concat!("split const_map ", stringify!($name), " can only use linear or sorted lookups"));
+    };
+
+    // The lookup function can have generic parameters and a `where` clause, which every generated
+    // function gets. They're taken out first, leaving the rest of the lookup function to be read
+    // as usual, and kept with the declaration. The `where` clause goes on until the types of the
+    // map or the reverse lookup function.
+    (@lookup $algo:tt $scope:tt $flags:tt $name:ident [$item:tt $len:tt $vcheck:tt $layout:ident $req:tt $valid:tt []], $($rest:tt)*) => {
+        $crate::const_map_impl!(@where [$algo $scope $flags $name [$item $len $vcheck $layout $req $valid]] [] $($rest)*);
+    };
+    (@where $pre:tt [$($spec:tt)*] , $($rest:tt)*) => {
+        $crate::const_map_impl!(@generics $pre [$($spec)*] [] , $($rest)*);
+    };
+    (@where $pre:tt [$($spec:tt)*] where $($rest:tt)*) => {
+        $crate::const_map_impl!(@bounds $pre [$($spec)*] [] $($rest)*);
+    };
+    (@where $pre:tt [$($spec:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::const_map_impl!(@where $pre [$($spec)* $next] $($rest)*);
+    };
+    (@bounds $pre:tt $spec:tt [$($wc:tt)*] , ($($types:tt)*) { $($entries:tt)* } $($rest:tt)*) => {
+        $crate::const_map_impl!(@generics $pre $spec [$($wc)*] , ($($types)*) { $($entries)* } $($rest)*);
+    };
+    (@bounds $pre:tt $spec:tt [$($wc:tt)*] , $(#[$attr:meta])* sorted $rlookup:ident() $($rest:tt)*) => {
+        $crate::const_map_impl!(@generics $pre $spec [$($wc)*] , $(#[$attr])* sorted $rlookup() $($rest)*);
+    };
+    (@bounds $pre:tt $spec:tt [$($wc:tt)*] , $(#[$attr:meta])* $rlookup:ident() $($rest:tt)*) => {
+        $crate::const_map_impl!(@generics $pre $spec [$($wc)*] , $(#[$attr])* $rlookup() $($rest)*);
+    };
+    (@bounds $pre:tt $spec:tt [$($wc:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::const_map_impl!(@bounds $pre $spec [$($wc)* $next] $($rest)*);
+    };
+    (@generics [$algo:tt $scope:tt $flags:tt $name:ident [$($decl:tt)*]] [$(#[$attr:meta])* $lookup:ident <$($gen:tt),*> $($spec:tt)*] $wc:tt $($rest:tt)*) => {
+        $crate::const_map_impl!(@lookup $algo $scope $flags $name [$($decl)* [[$($gen),*] $wc]], $(#[$attr])* $lookup $($spec)* $($rest)*);
+    };
+    (@generics [$algo:tt $scope:tt $flags:tt $name:ident [$($decl:tt)*]] [$(#[$attr:meta])* &$lookup:ident <$($gen:tt),*> $($spec:tt)*] $wc:tt $($rest:tt)*) => {
+        $crate::const_map_impl!(@lookup $algo $scope $flags $name [$($decl)* [[$($gen),*] $wc]], $(#[$attr])* &$lookup $($spec)* $($rest)*);
+    };
+    (@generics [$algo:tt $scope:tt $flags:tt $name:ident [$($decl:tt)*]] [$($spec:tt)*] $wc:tt $($rest:tt)*) => {
+        $crate::const_map_impl!(@lookup $algo $scope $flags $name [$($decl)* [[] $wc]], $($spec)* $($rest)*);
+    };
+    // The algorithm can also be chosen in brackets after the name of the lookup function, as long
+    // as it wasn't chosen with a keyword already.
+    (@lookup $algo:tt $scope:tt $flags:tt $name:ident $decl:tt, $(#[$attr:meta])* $lookup:ident() [$select:ident] $($rest:tt)*) => {
+        $crate::const_map_impl!(@select $algo $select $scope $flags $name $decl, $(#[$attr])* $lookup() $($rest)*);
+    };
+    (@lookup $algo:tt $scope:tt $flags:tt $name:ident $decl:tt, $(#[$attr:meta])* &$lookup:ident() [$select:ident] $($rest:tt)*) => {
+        $crate::const_map_impl!(@select $algo $select $scope $flags $name $decl, $(#[$attr])* &$lookup() $($rest)*);
+    };
+    // The lookup functions are generated the same way as the optional items, so they're added to
+    // the start of the list of those.
+    (@lookup $algo:tt $scope:tt $flags:tt $name:ident $decl:tt, $(#[$attr:meta])* $lookup:ident() normalize $norm:path, $($rest:tt)*) => {
+        $crate::const_map_impl!(@reverse $algo [] $flags $name $decl, $scope, [$(#[$attr])* normalize($norm): $lookup(),] $($rest)*);
+    };
+    (@lookup $algo:tt $scope:tt $flags:tt $name:ident $decl:tt, $(#[$attr:meta])* $lookup:ident() fallback $fallback:path, $($rest:tt)*) => {
+        $crate::const_map_impl!(@reverse $algo [] $flags $name $decl, $scope, [$(#[$attr])* fallback($fallback): $lookup(),] $($rest)*);
+    };
+    (@lookup $algo:tt $scope:tt $flags:tt $name:ident $decl:tt, $(#[$attr:meta])* $lookup:ident() or $default:expr, $($rest:tt)*) => {
+        $crate::const_map_impl!(@reverse $algo [] $flags $name $decl, $scope, [$(#[$attr])* or($default): $lookup(),] $($rest)*);
+    };
+    (@lookup $algo:tt $scope:tt $flags:tt $name:ident $decl:tt, $(#[$attr:meta])* &$lookup:ident() $(using $eq:path)?, $($rest:tt)*) => {
+        $crate::const_map_impl!(@reverse $algo [$($eq)?] $flags $name $decl, $scope, [$(#[$attr])* get_ref: $lookup(),] $($rest)*);
+    };
+    (@lookup $algo:tt $scope:tt $flags:tt $name:ident $decl:tt, $(#[$attr:meta])* $lookup:ident() $(using $eq:path)?, $($rest:tt)*) => {
+        $crate::const_map_impl!(@reverse $algo [$($eq)?] $flags $name $decl, $scope, [$(#[$attr])* get: $lookup(),] $($rest)*);
+    };
+
+    (@select [linear $check:ident] linear $scope:tt $flags:tt $name:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@lookup [linear $check] $scope $flags $name $($rest)*);
+    };
+    (@select [linear any] $select:ident $scope:tt $flags:tt $name:ident $($rest:tt)*) => {
+        compile_error!(concat!("multi const_map ", stringify!($name), " can only use linear search"));
+    };
+    (@select [linear $check:ident] binary $scope:tt $flags:tt $name:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@lookup [binary sorted] $scope $flags $name $($rest)*);
+    };
+    (@select [linear $check:ident] $select:ident $scope:tt $flags:tt $name:ident [$item:tt $len:tt $vcheck:tt split $req:tt $valid:tt $sig:tt], $($rest:tt)*) => {
+        compile_error!(concat!("split const_map ", stringify!($name), " can only use linear or sorted lookups"));
+    };
+    (@select [linear $check:ident] jump $scope:tt $flags:tt $name:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@lookup [jump dense] $scope $flags $name $($rest)*);
+    };
+    (@select [linear unique] hashed $scope:tt $flags:tt $name:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@lookup [hash hashed] $scope $flags $name $($rest)*);
+    };
+    (@select [linear sorted] hashed $scope:tt $flags:tt $name:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@lookup [hash sorted_hashed] $scope $flags $name $($rest)*);
+    };
+    (@select [linear $check:ident] fnv $scope:tt $flags:tt $name:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@lookup [fnv $check] $scope $flags $name $($rest)*);
+    };
+    (@select [linear $check:ident] match $scope:tt $flags:tt $name:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@lookup [match $check] $scope $flags $name $($rest)*);
+    };
+    (@select [linear $check:ident] $select:ident $scope:tt $flags:tt $name:ident $($rest:tt)*) => {
+        compile_error!(concat!("unknown lookup algorithm `", stringify!($select), "` for const_map ", stringify!($name)));
+    };
+    (@select $algo:tt $select:ident $scope:tt $flags:tt $name:ident $($rest:tt)*) => {
+        compile_error!(concat!("the lookup algorithm of const_map ", stringify!($name), " is chosen twice"));
+    };
+
+    // Values in ascending order are unique, so a sorted reverse lookup suits bijective maps too.
+    (@reverse $algo:tt $using:tt $flags:tt $name:ident $decl:tt, $scope:tt, [$($lookup:tt)*] $(#[$attr:meta])* sorted $rlookup:ident(), $types:tt $entries:tt $($rest:tt)*) => {
+        $crate::const_map_impl!(@opts [$algo $using $name $decl, $scope, $types $entries] [$($lookup)* $(#[$attr])* reverse_sorted: $rlookup(),] $($rest)*);
+    };
+    (@reverse $algo:tt $using:tt [] $name:ident $decl:tt, $scope:tt, [$($lookup:tt)*] $(#[$attr:meta])* $rlookup:ident() using $eq:path, $types:tt $entries:tt $($rest:tt)*) => {
+        $crate::const_map_impl!(@opts [$algo $using $name $decl, $scope, $types $entries] [$($lookup)* $(#[$attr])* reverse_using($eq): $rlookup(),] $($rest)*);
+    };
+    (@reverse $algo:tt $using:tt [bijective] $name:ident $decl:tt, $scope:tt, $lookup:tt $(#[$attr:meta])* $rlookup:ident() using $eq:path, $($rest:tt)*) => {
+        compile_error!(concat!("the reverse lookup function of bijective const_map ", stringify!($name), " can't use a comparison function"));
+    };
+    (@reverse $algo:tt $using:tt [] $name:ident $decl:tt, $scope:tt, [$($lookup:tt)*] $(#[$attr:meta])* $rlookup:ident(), $types:tt $entries:tt $($rest:tt)*) => {
+        $crate::const_map_impl!(@opts [$algo $using $name $decl, $scope, $types $entries] [$($lookup)* $(#[$attr])* reverse: $rlookup(),] $($rest)*);
+    };
+    (@reverse $algo:tt $using:tt [bijective] $name:ident $decl:tt, $scope:tt, [$($lookup:tt)*] $(#[$attr:meta])* $rlookup:ident(), $types:tt $entries:tt $($rest:tt)*) => {
+        $crate::const_map_impl!(@opts [$algo $using $name $decl, $scope, $types $entries] [$($lookup)* $(#[$attr])* reverse_unique: $rlookup(),] $($rest)*);
+    };
+    (@reverse $algo:tt $using:tt [] $name:ident $decl:tt, $scope:tt, $lookup:tt $types:tt $entries:tt $($rest:tt)*) => {
+        $crate::const_map_impl!(@opts [$algo $using $name $decl, $scope, $types $entries] $lookup $($rest)*);
+    };
+    (@reverse $algo:tt $using:tt [bijective] $name:ident $decl:tt, $($rest:tt)*) => {
+        compile_error!(concat!("bijective const_map ", stringify!($name), " needs a reverse lookup function"));
+    };
+
+    // Collect the optional items one at a time, so that a `;` after them can start another map.
+    // The `TryFrom` implementation is generated like an optional item, named after its error type.
+    (@opts [$algo:tt $using:tt $name:ident $decl:tt, [try_from], $types:tt $entries:tt] [$($opts:tt)*] $(; $($next:tt)*)?) => {
+        $crate::const_map_impl!(@opts [$algo $using $name $decl, [], $types $entries] [$($opts)* try_from: KeyNotFound,] $(; $($next)*)?);
+    };
+    (@opts [$algo:tt $using:tt $name:ident $decl:tt, [$($scope:ident)?], $types:tt $entries:tt] $opts:tt) => {
+        $crate::const_map_impl!(@key $algo $using $name $decl, $($scope::)?$name, $types $entries, $opts);
+    };
+    (@opts [$algo:tt $using:tt $name:ident $decl:tt, $scope:tt, $types:tt $entries:tt] $opts:tt; $($next:tt)*) => {
+        $crate::const_map_impl!(@opts [$algo $using $name $decl, $scope, $types $entries] $opts);
+        $crate::const_map_impl!(@head $scope [] [unique] $($next)*);
+    };
+    // Associated types can't be defined in an `impl` block, so this one only works at module level.
+    (@opts [$algo:tt $using:tt $name:ident $decl:tt, [Self], $types:tt $entries:tt] $opts:tt array_type: $($rest:tt)*) => {
+        compile_error!(concat!("the `array_type` option of const_map ", stringify!($name), " can only be used with free_const_map!"));
+    };
+    (@opts $state:tt $opts:tt, $($rest:tt)*) => {
+        $crate::const_map_impl!(@opts $state $opts $($rest)*);
+    };
+    (@opts $state:tt [$($opts:tt)*] $(#[$attr:meta])* $kind:ident($($args:tt)*): $fname:ident() $($rest:tt)*) => {
+        $crate::const_map_impl!(@opts $state [$($opts)* $(#[$attr])* $kind($($args)*): $fname(),] $($rest)*);
+    };
+    (@opts $state:tt [$($opts:tt)*] $(#[$attr:meta])* $kind:ident: $fname:ident() $($rest:tt)*) => {
+        $crate::const_map_impl!(@opts $state [$($opts)* $(#[$attr])* $kind: $fname(),] $($rest)*);
+    };
+    (@opts $state:tt [$($opts:tt)*] $(#[$attr:meta])* $kind:ident: $cname:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@opts $state [$($opts)* $(#[$attr])* $kind: $cname,] $($rest)*);
+    };
+    // Checking the values are unique doesn't generate an item. It's done where the length of the
+    // map is worked out, like the checks on the keys, so it's recorded along with what the map is
+    // declared with.
+    (@opts [$algo:tt $using:tt $name:ident [$item:tt $len:tt [] $layout:ident $req:tt $valid:tt $sig:tt], $scope:tt, $types:tt $entries:tt] $opts:tt unique_values $($rest:tt)*) => {
+        $crate::const_map_impl!(@opts [$algo $using $name [$item $len [unique] $layout $req $valid $sig], $scope, $types $entries] $opts $($rest)*);
+    };
+    // Likewise for required keys and validators, which can be given more than once.
+    (@opts [$algo:tt $using:tt $name:ident [$item:tt $len:tt $vcheck:tt $layout:ident [$($req:expr),*] $valid:tt $sig:tt], $scope:tt, $types:tt $entries:tt] $opts:tt require($($key:expr),+ $(,)?) $($rest:tt)*) => {
+        $crate::const_map_impl!(@opts [$algo $using $name [$item $len $vcheck $layout [$($req,)* $($key),+] $valid $sig], $scope, $types $entries] $opts $($rest)*);
+    };
+    (@opts [$algo:tt $using:tt $name:ident [$item:tt $len:tt $vcheck:tt $layout:ident $req:tt [$($valid:path),*] $sig:tt], $scope:tt, $types:tt $entries:tt] $opts:tt validate($validator:path) $($rest:tt)*) => {
+        $crate::const_map_impl!(@opts [$algo $using $name [$item $len $vcheck $layout $req [$($valid,)* $validator] $sig], $scope, $types $entries] $opts $($rest)*);
+    };
+    // Hiding the items of the options which come after `doc_hidden` is done by adding
+    // `#[doc(hidden)]` to each of them, up to the end of the map's options.
+    (@opts $state:tt $opts:tt doc_hidden $($rest:tt)*) => {
+        $crate::const_map_impl!(@hidden $state $opts [] $($rest)*);
+    };
+    (@hidden $state:tt $opts:tt [$($done:tt)*] , $($rest:tt)*) => {
+        $crate::const_map_impl!(@hidden $state $opts [$($done)* ,] $($rest)*);
+    };
+    (@hidden $state:tt $opts:tt [$($done:tt)*] $(#[$attr:meta])* $kind:ident($($args:tt)*): $fname:ident() $($rest:tt)*) => {
+        $crate::const_map_impl!(@hidden $state $opts [$($done)* #[doc(hidden)] $(#[$attr])* $kind($($args)*): $fname()] $($rest)*);
+    };
+    (@hidden $state:tt $opts:tt [$($done:tt)*] $(#[$attr:meta])* $kind:ident: $fname:ident() $($rest:tt)*) => {
+        $crate::const_map_impl!(@hidden $state $opts [$($done)* #[doc(hidden)] $(#[$attr])* $kind: $fname()] $($rest)*);
+    };
+    (@hidden $state:tt $opts:tt [$($done:tt)*] $(#[$attr:meta])* $kind:ident: $cname:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@hidden $state $opts [$($done)* #[doc(hidden)] $(#[$attr])* $kind: $cname] $($rest)*);
+    };
+    (@hidden $state:tt $opts:tt [$($done:tt)*] $kind:ident($($args:tt)*) $($rest:tt)*) => {
+        $crate::const_map_impl!(@hidden $state $opts [$($done)* $kind($($args)*)] $($rest)*);
+    };
+    (@hidden $state:tt $opts:tt [$($done:tt)*] $kind:ident $($rest:tt)*) => {
+        $crate::const_map_impl!(@hidden $state $opts [$($done)* $kind] $($rest)*);
+    };
+    (@hidden $state:tt $opts:tt [$($done:tt)*] $(; $($next:tt)*)?) => {
+        $crate::const_map_impl!(@opts $state $opts $($done)* $(; $($next)*)?);
+    };
+
+    // Work out how to compare keys, and what type the functions should take them as.
+    // Keys are always constants, so string keys are stored as `&'static`, whatever lifetime they're
+    // written with. The lookup functions take them with any lifetime anyway.
+    (@key $algo:tt $using:tt $name:ident $decl:tt, $map:path, (ci &$lt:lifetime str => $($vty:tt)*) $entries:tt, $opts:tt) => {
+        $crate::const_map_impl!(@using $algo $using $name $decl, $map, [ci] [&'static str, &str], ($($vty)*) $entries, $opts);
+    };
+    (@key $algo:tt $using:tt $name:ident $decl:tt, $map:path, (&$lt:lifetime [u8] => $($vty:tt)*) $entries:tt, $opts:tt) => {
+        $crate::const_map_impl!(@using $algo $using $name $decl, $map, [bytes] [&'static [u8], &[u8]], ($($vty)*) $entries, $opts);
+    };
+    (@key $algo:tt $using:tt $name:ident $decl:tt, $map:path, (&$lt:lifetime str => $($vty:tt)*) $entries:tt, $opts:tt) => {
+        $crate::const_map_impl!(@using $algo $using $name $decl, $map, [str] [&'static str, &str], ($($vty)*) $entries, $opts);
+    };
+    (@key [linear $check:ident] $using:tt $name:ident $decl:tt, $map:path, (ref $kty:ty => $($vty:tt)*) $entries:tt, $opts:tt) => {
+        $crate::const_map_impl!(@using [linear $check] $using $name $decl, $map, [(ref eq)] [$kty, &$kty], ($($vty)*) $entries, $opts);
+    };
+    (@key [binary $check:ident] $using:tt $name:ident $decl:tt, $map:path, (ref $kty:ty => $($vty:tt)*) $entries:tt, $opts:tt) => {
+        $crate::const_map_impl!(@using [binary $check] $using $name $decl, $map, [(ref eq)] [$kty, &$kty], ($($vty)*) $entries, $opts);
+    };
+    (@key $algo:tt $using:tt $name:ident $decl:tt, $map:path, (ref $kty:ty => $($vty:tt)*) $entries:tt, $opts:tt) => {
+        compile_error!(concat!("const_map ", stringify!($name), " can only take keys by reference for linear or sorted lookups"));
+    };
+    (@key [linear $check:ident] $using:tt $name:ident $decl:tt, $map:path, (range $kty:ty => $($vty:tt)*) $entries:tt, $opts:tt) => {
+        $crate::const_map_impl!(@using [linear $check] $using $name $decl, $map, [(range eq)] [::core::ops::RangeInclusive<$kty>, $kty], ($($vty)*) $entries, $opts);
+    };
+    (@key [binary $check:ident] $using:tt $name:ident $decl:tt, $map:path, (range $kty:ty => $($vty:tt)*) $entries:tt, $opts:tt) => {
+        $crate::const_map_impl!(@using [binary $check] $using $name $decl, $map, [(range eq)] [::core::ops::RangeInclusive<$kty>, $kty], ($($vty)*) $entries, $opts);
+    };
+    (@key $algo:tt $using:tt $name:ident $decl:tt, $map:path, (range $kty:ty => $($vty:tt)*) $entries:tt, $opts:tt) => {
+        compile_error!(concat!("const_map ", stringify!($name), " can only have range keys for linear or sorted lookups"));
+    };
+    (@key $algo:tt $using:tt $name:ident $decl:tt, $map:path, (f32 => $($vty:tt)*) $entries:tt, $opts:tt) => {
+        $crate::const_map_impl!(@float $algo $using $name $decl, $map, [f32], ($($vty)*) $entries, $opts);
+    };
+    (@key $algo:tt $using:tt $name:ident $decl:tt, $map:path, (f64 => $($vty:tt)*) $entries:tt, $opts:tt) => {
+        $crate::const_map_impl!(@float $algo $using $name $decl, $map, [f64], ($($vty)*) $entries, $opts);
+    };
+    (@key $algo:tt $using:tt $name:ident $decl:tt, $map:path, (($a:ty, $b:ty) => $($vty:tt)*) $entries:tt, $opts:tt) => {
+        $crate::const_map_impl!(@tuple $algo $using $name $decl, $map, [0 1] [($a, $b)], ($($vty)*) $entries, $opts);
+    };
+    (@key $algo:tt $using:tt $name:ident $decl:tt, $map:path, (($a:ty, $b:ty, $c:ty) => $($vty:tt)*) $entries:tt, $opts:tt) => {
+        $crate::const_map_impl!(@tuple $algo $using $name $decl, $map, [0 1 2] [($a, $b, $c)], ($($vty)*) $entries, $opts);
+    };
+    (@key $algo:tt $using:tt $name:ident $decl:tt, $map:path, (($a:ty, $b:ty, $c:ty, $d:ty) => $($vty:tt)*) $entries:tt, $opts:tt) => {
+        $crate::const_map_impl!(@tuple $algo $using $name $decl, $map, [0 1 2 3] [($a, $b, $c, $d)], ($($vty)*) $entries, $opts);
+    };
+    (@key $algo:tt $using:tt $name:ident $decl:tt, $map:path, (enum $kty:ty => $($vty:tt)*) $entries:tt, $opts:tt) => {
+        $crate::const_map_impl!(@using $algo $using $name $decl, $map, [enum] [$kty, $kty], ($($vty)*) $entries, $opts);
+    };
+    (@key $algo:tt $using:tt $name:ident $decl:tt, $map:path, ($kty:ty => $($vty:tt)*) $entries:tt, $opts:tt) => {
+        $crate::const_map_impl!(@using $algo $using $name $decl, $map, [eq] [$kty, $kty], ($($vty)*) $entries, $opts);
+    };
+
+    // Floats are compared by their total order rather than with `==`, which would make `NaN` keys
+    // impossible to find. Neither `jump` nor `match` can use that.
+    (@float [jump $check:ident] $using:tt $name:ident $($rest:tt)*) => {
+        compile_error!(concat!("jump const_map ", stringify!($name), " can't have float keys"));
+    };
+    (@float [match $check:ident] $using:tt $name:ident $($rest:tt)*) => {
+        compile_error!(concat!("match const_map ", stringify!($name), " can't have float keys"));
+    };
+    (@float $algo:tt $using:tt $name:ident $decl:tt, $map:path, [$fty:ident], $($rest:tt)*) => {
+        $crate::const_map_impl!(@using $algo $using $name $decl, $map, [$fty] [$fty, $fty], $($rest)*);
+    };
+
+    // Tuples are compared one element at a time, in order. Only linear search and binary search can
+    // do that.
+    (@tuple [linear $check:ident] $using:tt $name:ident $decl:tt, $map:path, [$($i:tt)*] [$kty:ty], $($rest:tt)*) => {
+        $crate::const_map_impl!(@using [linear $check] $using $name $decl, $map, [(tuple $($i)*)] [$kty, $kty], $($rest)*);
+    };
+    (@tuple [binary $check:ident] $using:tt $name:ident $decl:tt, $map:path, [$($i:tt)*] [$kty:ty], $($rest:tt)*) => {
+        $crate::const_map_impl!(@using [binary $check] $using $name $decl, $map, [(tuple $($i)*)] [$kty, $kty], $($rest)*);
+    };
+    (@tuple $algo:tt $using:tt $name:ident $($rest:tt)*) => {
+        compile_error!(concat!("const_map ", stringify!($name), " can only have tuple keys for linear or sorted lookups"));
+    };
+
+    // A custom comparison function replaces the one chosen from the key type. Only linear search
+    // can use it, since the other algorithms need more than just checking for equality.
+    (@using $algo:tt [$eq:path] $name:ident $decl:tt, $map:path, [(range $kcmp:tt)] $($rest:tt)*) => {
+        compile_error!(concat!("const_map ", stringify!($name), " can't use a custom comparison function for range keys"));
+    };
+    (@using [linear unique] [$eq:path] $name:ident $decl:tt, $map:path, [(ref $kcmp:tt)] $($rest:tt)*) => {
+        $crate::const_map_impl!(@value [linear unique] $name $decl, $map, [(ref (using $eq))] $($rest)*);
+    };
+    (@using [linear unique] [$eq:path] $name:ident $decl:tt, $map:path, $kcmp:tt $($rest:tt)*) => {
+        $crate::const_map_impl!(@value [linear unique] $name $decl, $map, [(using $eq)] $($rest)*);
+    };
+    (@using $algo:tt [$eq:path] $name:ident $decl:tt, $($rest:tt)*) => {
+        compile_error!(concat!("const_map ", stringify!($name), " can only use a custom comparison function for linear search"));
+    };
+    (@using $algo:tt [] $name:ident $decl:tt, $map:path, $($rest:tt)*) => {
+        $crate::const_map_impl!(@value $algo $name $decl, $map, $($rest)*);
+    };
+
+    // Likewise for values.
+    (@value [$algo:ident $check:ident] $name:ident $decl:tt, $map:path, [$kcmp:tt] [$kty:ty, $kpty:ty], (&'static str) { $($entries:tt)* }, $opts:tt) => {
+        $crate::normalize_entries!($crate [([$algo $kcmp str] $name, $map, [$kty, $kpty, &'static str, &str]) [$check] $decl $opts] $($entries)*);
+    };
+    (@value [$algo:ident $check:ident] $name:ident $decl:tt, $map:path, [$kcmp:tt] [$kty:ty, $kpty:ty], (&'static [u8]) { $($entries:tt)* }, $opts:tt) => {
+        $crate::normalize_entries!($crate [([$algo $kcmp bytes] $name, $map, [$kty, $kpty, &'static [u8], &[u8]]) [$check] $decl $opts] $($entries)*);
+    };
+    (@value [$algo:ident $check:ident] $name:ident $decl:tt, $map:path, [$kcmp:tt] [$kty:ty, $kpty:ty], (f32) { $($entries:tt)* }, $opts:tt) => {
+        $crate::normalize_entries!($crate [([$algo $kcmp f32] $name, $map, [$kty, $kpty, f32, f32]) [$check] $decl $opts] $($entries)*);
+    };
+    (@value [$algo:ident $check:ident] $name:ident $decl:tt, $map:path, [$kcmp:tt] [$kty:ty, $kpty:ty], (f64) { $($entries:tt)* }, $opts:tt) => {
+        $crate::normalize_entries!($crate [([$algo $kcmp f64] $name, $map, [$kty, $kpty, f64, f64]) [$check] $decl $opts] $($entries)*);
+    };
+    (@value [$algo:ident $check:ident] $name:ident $decl:tt, $map:path, [$kcmp:tt] [$kty:ty, $kpty:ty], (enum $vty:ty) { $($entries:tt)* }, $opts:tt) => {
+        $crate::normalize_entries!($crate [([$algo $kcmp enum] $name, $map, [$kty, $kpty, $vty, $vty]) [$check] $decl $opts] $($entries)*);
+    };
+    (@value [$algo:ident $check:ident] $name:ident $decl:tt, $map:path, [$kcmp:tt] [$kty:ty, $kpty:ty], ($vty:ty) { $($entries:tt)* }, $opts:tt) => {
+        $crate::normalize_entries!($crate [([$algo $kcmp eq] $name, $map, [$kty, $kpty, $vty, $vty]) [$check] $decl $opts] $($entries)*);
+    };
+
+    // Normalize the entries to `[attributes] key => value,` so they can be parsed unambiguously.
+    // Doc comments are dropped, since they don't do anything on expressions. With the `proc-macro`
+    // feature, `normalize_entries!` does this all at once and only the last arm is used.
+    (@entries $state:tt [$($done:tt)*] [$($attrs:tt)*] #[doc = $doc:expr] $($rest:tt)*) => {
+        $crate::const_map_impl!(@entries $state [$($done)*] [$($attrs)*] $($rest)*);
+    };
+    (@entries $state:tt [$($done:tt)*] [$($attrs:tt)*] #[$m:meta] $($rest:tt)*) => {
+        $crate::const_map_impl!(@entries $state [$($done)*] [$($attrs)* #[$m]] $($rest)*);
+    };
+    // A last entry of `_ => value` isn't part of the map, but gives the value the lookup function
+    // returns for keys which aren't in it, the same as `lookup() or value`.
+    (@entries $state:tt $done:tt [] _ => $default:expr $(,)?) => {
+        $crate::const_map_impl!(@default $state $done $default);
+    };
+    (@entries [([$($algo:tt)*] $name:ident, $($rest:tt)*) $($state:tt)*] $done:tt [$($attrs:tt)+] _ => $($entries:tt)*) => {
+        compile_error!(concat!("the `_` entry of const_map ", stringify!($name), " can't have attributes"));
+    };
+    (@entries [([$($algo:tt)*] $name:ident, $($rest:tt)*) $($state:tt)*] $done:tt [] _ => $($entries:tt)*) => {
+        compile_error!(concat!("the `_` entry of const_map ", stringify!($name), " must be the last one"));
+    };
+    (@entries $state:tt [$($done:tt)*] [$($attrs:tt)*] $k:expr => $v:expr $(, $($rest:tt)*)?) => {
+        $crate::const_map_impl!(@entries $state [$($done)* [$($attrs)*] $k => $v,] [] $($($rest)*)?);
+    };
+    (@entries [($($ctx:tt)*) $($state:tt)*] [$($done:tt)*] []) => {
+        $crate::const_map_impl!(@emit ($($ctx)*, { $($done)* }) $($state)*);
+    };
+
+    // The lookup function is the first of the optional items.
+    (@default [($($ctx:tt)*) $check:tt $decl:tt [$(#[$attr:meta])* get: $lookup:ident(), $($opts:tt)*]] [$($done:tt)*] $default:expr) => {
+        $crate::const_map_impl!(@emit ($($ctx)*, { $($done)* }) $check $decl [$(#[$attr])* or($default): $lookup(), $($opts)*]);
+    };
+    (@default [([$algo:ident $kcmp:tt $vcmp:ident] $name:ident, $($ctx:tt)*) $($state:tt)*] $done:tt $default:expr) => {
+        compile_error!(concat!("const_map ", stringify!($name), " can only have a `_` entry if its lookup function is written as `lookup()`"));
+    };
+
+    (@emit ([$algo:ident $kcmp:tt $vcmp:ident] $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$check:ident] [$item:tt [$($len:expr)?] [$($vcheck:ident)?] pairs [$($req:expr),*] [$($valid:path),*] [[$($gen:tt),*] [$($wc:tt)*]]] [$($opts:tt)*]) => {
+        pub $item $name: [($kty, $vty); $crate::expect_len!(
+            $name,
+            $crate::validated_len!(
+                $name, [$kty, $vty], [$([$(#[$m])*] ($k, $v)),*], [$($valid),*],
+                $crate::checked_values!(
+                    [$($vcheck)?] [$vcmp] $name, $vty, [$([$(#[$m])*] $v),*],
+                    $crate::required_len!(
+                        [$kcmp] $name, $kty, $kpty, [$([$(#[$m])*] $k),*], [$($req),*],
+                        $crate::checked_len!([$check $kcmp] $name, $kty, $([$(#[$m])*] $k),*)
+                    )
+                )
+            )
+            $(, $len)?
+        )] =
+            [$($(#[$m])* ($k, $v)),*];
+
+        $crate::const_map_options!(
+            ([$algo $kcmp $vcmp] pairs $name, $map, [$kty, $kpty, $vty, $vpty], [$($gen),*] [$($wc)*], { $([$(#[$m])*] $k => $v,)* });
+            $($opts)*
+        );
+    };
+    (@emit ([$algo:ident $kcmp:tt $vcmp:ident] $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$check:ident] [$item:tt [$($len:expr)?] [$($vcheck:ident)?] split [$($req:expr),*] [$($valid:path),*] [[$($gen:tt),*] [$($wc:tt)*]]] [$($opts:tt)*]) => {
+        pub $item $name: $crate::Split<$kty, $vty, {
+            $crate::expect_len!(
+                $name,
+                $crate::validated_len!(
+                    $name, [$kty, $vty], [$([$(#[$m])*] ($k, $v)),*], [$($valid),*],
+                    $crate::checked_values!(
+                        [$($vcheck)?] [$vcmp] $name, $vty, [$([$(#[$m])*] $v),*],
+                        $crate::required_len!(
+                            [$kcmp] $name, $kty, $kpty, [$([$(#[$m])*] $k),*], [$($req),*],
+                            $crate::checked_len!([$check $kcmp] $name, $kty, $([$(#[$m])*] $k),*)
+                        )
+                    )
+                )
+                $(, $len)?
+            )
+        }> = $crate::Split {
+            keys: [$($(#[$m])* $k),*],
+            values: [$($(#[$m])* $v),*],
+        };
+
+        $crate::const_map_options!(
+            ([$algo $kcmp $vcmp] split $name, $map, [$kty, $kpty, $vty, $vpty], [$($gen),*] [$($wc)*], { $([$(#[$m])*] $k => $v,)* });
+            $($opts)*
+        );
+    };
+    (@emit ([$algo:ident $kcmp:tt $vcmp:ident] $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$check:ident] [$item:tt [$($len:expr)?] [$($vcheck:ident)?] interned [$($req:expr),*] [$($valid:path),*] [[$($gen:tt),*] [$($wc:tt)*]]] [$($opts:tt)*]) => {
+        pub $item $name: $crate::Interned<$kty, $vty, {
+            $crate::expect_len!(
+                $name,
+                $crate::validated_len!(
+                    $name, [$kty, $vty], [$([$(#[$m])*] ($k, $v)),*], [$($valid),*],
+                    $crate::checked_values!(
+                        [$($vcheck)?] [$vcmp] $name, $vty, [$([$(#[$m])*] $v),*],
+                        $crate::required_len!(
+                            [$kcmp] $name, $kty, $kpty, [$([$(#[$m])*] $k),*], [$($req),*],
+                            $crate::checked_len!([$check $kcmp] $name, $kty, $([$(#[$m])*] $k),*)
+                        )
+                    )
+                )
+                $(, $len)?
+            )
+        }, {
+            $crate::distinct_count!([$vcmp] $vty, $([$(#[$m])*] $v),*)
+        }> = $crate::interned!([$vcmp] $kty, $vty, $([$(#[$m])*] $k => $v),*);
+
+        $crate::const_map_options!(
+            ([$algo $kcmp $vcmp] interned $name, $map, [$kty, $kpty, $vty, $vpty], [$($gen),*] [$($wc)*], { $([$(#[$m])*] $k => $v,)* });
+            $($opts)*
+        );
+    };
+}
+
+/// Generates the optional items listed after the entries of the map, each of which is written as
+/// `kind: name()` for a function or `kind: NAME` for a constant, with the kind optionally taking
+/// arguments.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! const_map_options {
+    ($ctx:tt;) => {};
+    ($ctx:tt; $(#[$attr:meta])* $kind:ident($($args:tt)*): $fname:ident() $(, $($rest:tt)*)?) => {
+        $crate::const_map_option!($ctx [$(#[$attr])*] $kind($($args)*) $fname);
+        $crate::const_map_options!($ctx; $($($rest)*)?);
+    };
+    ($ctx:tt; $(#[$attr:meta])* $kind:ident: $fname:ident() $(, $($rest:tt)*)?) => {
+        $crate::const_map_option!($ctx [$(#[$attr])*] $kind $fname);
+        $crate::const_map_options!($ctx; $($($rest)*)?);
+    };
+    ($ctx:tt; $(#[$attr:meta])* $kind:ident: $cname:ident $(, $($rest:tt)*)?) => {
+        $crate::const_map_option!($ctx [$(#[$attr])*] $kind const $cname);
+        $crate::const_map_options!($ctx; $($($rest)*)?);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! const_map_option {
+    ($ctx:tt $attrs:tt is_known $fname:ident) => {
+        $crate::const_map_option!($ctx $attrs contains_key $fname);
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] get $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(key: $kpty) -> Option<$vty> where $($wc)* {
+            match $crate::search!([$algo $kcmp] $layout $map, $kty, $kpty, key, [$([$(#[$m])*] $k),*]) {
+                Some(i) => Some($crate::value_at!($layout $map, i)),
+                None => None,
+            }
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] from_str $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(s: &str) -> Option<$vty> where $($wc)* {
+            match $crate::search!([$algo $kcmp] $layout $map, $kty, $kpty, s, [$([$(#[$m])*] $k),*]) {
+                Some(i) => Some($crate::value_at!($layout $map, i)),
+                None => None,
+            }
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] get_ref $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(key: $kpty) -> Option<&'static $vty> where $($wc)* {
+            match $crate::search!([$algo $kcmp] $layout $map, $kty, $kpty, key, [$([$(#[$m])*] $k),*]) {
+                Some(i) => Some(&$crate::value_at!($layout $map, i)),
+                None => None,
+            }
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] get_key_value $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(key: $kpty) -> Option<($kty, $vty)> where $($wc)* {
+            match $crate::search!([$algo $kcmp] $layout $map, $kty, $kpty, key, [$([$(#[$m])*] $k),*]) {
+                Some(i) => Some(($crate::key_at!($layout $map, i), $crate::value_at!($layout $map, i))),
+                None => None,
+            }
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] canonicalize $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(key: $kpty) -> Option<$kty> where $($wc)* {
+            match $crate::search!([$algo $kcmp] $layout $map, $kty, $kpty, key, [$([$(#[$m])*] $k),*]) {
+                Some(i) => Some($crate::key_at!($layout $map, i)),
+                None => None,
+            }
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] try_get $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(key: $kpty) -> ::core::result::Result<$vty, $crate::NotFound<$kpty>> where $($wc)* {
+            match $crate::search!([$algo $kcmp] $layout $map, $kty, $kpty, key, [$([$(#[$m])*] $k),*]) {
+                Some(i) => Ok($crate::value_at!($layout $map, i)),
+                None => Err($crate::NotFound(key)),
+            }
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] contains_key $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(key: $kpty) -> bool where $($wc)* {
+            $crate::search!([$algo $kcmp] $layout $map, $kty, $kpty, key, [$([$(#[$m])*] $k),*]).is_some()
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] contains_all $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(keys: &[$kpty]) -> bool where $($wc)* {
+            let mut i = 0;
+            while i < keys.len() {
+                if $crate::search!([$algo $kcmp] $layout $map, $kty, $kpty, keys[i], [$([$(#[$m])*] $k),*]).is_none() {
+                    return false;
+                }
+                i += 1;
+            }
+            true
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] contains_any $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(keys: &[$kpty]) -> bool where $($wc)* {
+            let mut i = 0;
+            while i < keys.len() {
+                if $crate::search!([$algo $kcmp] $layout $map, $kty, $kpty, keys[i], [$([$(#[$m])*] $k),*]).is_some() {
+                    return true;
+                }
+                i += 1;
+            }
+            false
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] many $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen,)* const M: usize>(keys: [$kpty; M]) -> [Option<$vty>; M] where $($wc)* {
+            let mut values = [None; M];
+            let mut i = 0;
+            while i < M {
+                if let Some(j) = $crate::search!([$algo $kcmp] pairs $map, $kty, $kpty, keys[i], [$([$(#[$m])*] $k),*]) {
+                    values[i] = Some($map[j].1);
+                }
+                i += 1;
+            }
+            values
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] position $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(key: $kpty) -> Option<usize> where $($wc)* {
+            $crate::search!([$algo $kcmp] $layout $map, $kty, $kpty, key, [$([$(#[$m])*] $k),*])
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] get_all $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(key: $kpty, out: &mut [$vty]) -> usize where $($wc)* {
+            let pairs: &[($kty, $vty)] = &$map;
+            let mut found = 0;
+            let mut i = 0;
+            while i < pairs.len() {
+                if $crate::probe_eq!([$kcmp] pairs[i].0, key) {
+                    if found < out.len() {
+                        out[found] = pairs[i].1;
+                    }
+                    found += 1;
+                }
+                i += 1;
+            }
+            found
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] keys_for $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(value: $vpty, out: &mut [$kty]) -> usize where $($wc)* {
+            let pairs: &[($kty, $vty)] = &$map;
+            let mut found = 0;
+            let mut i = 0;
+            while i < pairs.len() {
+                if $crate::const_eq!([$vcmp] pairs[i].1, value) {
+                    if found < out.len() {
+                        out[found] = pairs[i].0;
+                    }
+                    found += 1;
+                }
+                i += 1;
+            }
+            found
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] contains_value $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(value: $vpty) -> bool where $($wc)* {
+            $crate::search_values!([$vcmp] &$map, $vty, $vpty, value).is_some()
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] or($default:expr) $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(key: $kpty) -> $vty where $($wc)* {
+            match $crate::search!([$algo $kcmp] $layout $map, $kty, $kpty, key, [$([$(#[$m])*] $k),*]) {
+                Some(i) => $crate::value_at!($layout $map, i),
+                None => $default,
+            }
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] flatten $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(key: $kpty) -> $vty where $($wc)* {
+            match $crate::search!([$algo $kcmp] $layout $map, $kty, $kpty, key, [$([$(#[$m])*] $k),*]) {
+                Some(i) => $crate::value_at!($layout $map, i),
+                None => None,
+            }
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] or_else($fallback:path) $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(key: $kpty) -> $vty where $($wc)* {
+            match $crate::search!([$algo $kcmp] $layout $map, $kty, $kpty, key, [$([$(#[$m])*] $k),*]) {
+                Some(i) => $crate::value_at!($layout $map, i),
+                None => $fallback(key),
+            }
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] fallback($fallback:path) $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(key: $kpty) -> Option<$vty> where $($wc)* {
+            match $crate::search!([$algo $kcmp] $layout $map, $kty, $kpty, key, [$([$(#[$m])*] $k),*]) {
+                Some(i) => Some($crate::value_at!($layout $map, i)),
+                None => $fallback(key),
+            }
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] normalize($norm:path) $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(key: $kpty) -> Option<$vty> where $($wc)* {
+            let key = $norm(key);
+            match $crate::search!([$algo $kcmp] $layout $map, $kty, $kpty, key, [$([$(#[$m])*] $k),*]) {
+                Some(i) => Some($crate::value_at!($layout $map, i)),
+                None => None,
+            }
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] get_or $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(key: $kpty, default: $vty) -> $vty where $($wc)* {
+            match $crate::search!([$algo $kcmp] $layout $map, $kty, $kpty, key, [$([$(#[$m])*] $k),*]) {
+                Some(i) => $crate::value_at!($layout $map, i),
+                None => default,
+            }
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] must $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(key: $kpty) -> $vty where $($wc)* {
+            match $crate::search!([$algo $kcmp] $layout $map, $kty, $kpty, key, [$([$(#[$m])*] $k),*]) {
+                Some(i) => $crate::value_at!($layout $map, i),
+                None => panic!(concat!("key not found in const_map ", stringify!($name))),
+            }
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] expect $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(key: $kpty, msg: &'static str) -> $vty where $($wc)* {
+            match $crate::search!([$algo $kcmp] $layout $map, $kty, $kpty, key, [$([$(#[$m])*] $k),*]) {
+                Some(i) => $crate::value_at!($layout $map, i),
+                None => panic!("{}", msg),
+            }
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] try_from const $ename:ident) => {
+        // The error type is only named by the implementation, so it's kept out of the way.
+        const _: () = {
+            #[doc = concat!("The error returned when converting a key which isn't in const_map ", stringify!($name), ".")]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            pub struct $ename;
+
+            impl ::core::fmt::Display for $ename {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.write_str(concat!("key not found in const_map ", stringify!($name)))
+                }
+            }
+
+            impl ::core::error::Error for $ename {}
+
+            $(#[$attr])*
+            impl ::core::convert::TryFrom<$kpty> for $vty {
+                type Error = $ename;
+
+                fn try_from(key: $kpty) -> ::core::result::Result<Self, Self::Error> {
+                    match $crate::search!([$algo $kcmp] $layout $map, $kty, $kpty, key, [$([$(#[$m])*] $k),*]) {
+                        Some(i) => Ok($crate::value_at!($layout $map, i)),
+                        None => Err($ename),
+                    }
+                }
+            }
+        };
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] reverse $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(value: $vpty) -> Option<$kty> where $($wc)* {
+            match $crate::search_values!([$vcmp] &$map, $vty, $vpty, value) {
+                Some(i) => Some($map[i].0),
+                None => None,
+            }
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] reverse_unique $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(value: $vpty) -> Option<$kty> where $($wc)* {
+            $crate::const_assert!(
+                $crate::all_unique!([$vcmp] $vty, $([$(#[$m])*] $v),*),
+                concat!("duplicate value in const_map ", stringify!($name))
+            );
+            match $crate::search_values!([$vcmp] &$map, $vty, $vpty, value) {
+                Some(i) => Some($map[i].0),
+                None => None,
+            }
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] reverse_sorted $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(value: $vpty) -> Option<$kty> where $($wc)* {
+            $crate::const_assert!(
+                $crate::values_sorted!([$vcmp] $vty, $([$(#[$m])*] $v),*),
+                concat!("values of const_map ", stringify!($name), " are out of order")
+            );
+            match $crate::search_values!([$vcmp] sorted &$map, $vty, $vpty, value) {
+                Some(i) => Some($map[i].0),
+                None => None,
+            }
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] reverse_using($eq:path) $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(value: $vty) -> Option<$kty> where $($wc)* {
+            let pairs: &[($kty, $vty)] = &$map;
+            let mut i = 0;
+            while i < pairs.len() {
+                if $eq(&pairs[i].1, &value) {
+                    return Some(pairs[i].0);
+                }
+                i += 1;
+            }
+            None
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] keys $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>() -> [$kty; $crate::count!($([$(#[$m])*] $k)*)] where $($wc)* {
+            [$($(#[$m])* $k),*]
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] values $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>() -> [$vty; $crate::count!($([$(#[$m])*] $k)*)] where $($wc)* {
+            [$($(#[$m])* $v),*]
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] values_sorted $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>() -> [$vty; $crate::count!($([$(#[$m])*] $k)*)] where $($wc)* {
+            $crate::sorted_values!([$vcmp] $vty, $([$(#[$m])*] $v),*)
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] inverse $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>() -> [($vty, $kty); $crate::count!($([$(#[$m])*] $k)*)] where $($wc)* {
+            [$($(#[$m])* ($v, $k)),*]
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] inverse const $cname:ident) => {
+        $(#[$attr])*
+        pub const $cname: [($vty, $kty); $crate::count!($([$(#[$m])*] $k)*)] = [$($(#[$m])* ($v, $k)),*];
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] inverse_sorted $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>() -> [($vty, $kty); $crate::count!($([$(#[$m])*] $k)*)] where $($wc)* {
+            $crate::sorted_values!([$vcmp] ($vty, $kty), $([$(#[$m])*] ($v, $k)),* => .0)
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] inverse_sorted const $cname:ident) => {
+        $(#[$attr])*
+        pub const $cname: [($vty, $kty); $crate::count!($([$(#[$m])*] $k)*)] =
+            $crate::sorted_values!([$vcmp] ($vty, $kty), $([$(#[$m])*] ($v, $k)),* => .0);
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] keys const $cname:ident) => {
+        $(#[$attr])*
+        pub const $cname: [$kty; $crate::count!($([$(#[$m])*] $k)*)] = [$($(#[$m])* $k),*];
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] values const $cname:ident) => {
+        $(#[$attr])*
+        pub const $cname: [$vty; $crate::count!($([$(#[$m])*] $k)*)] = [$($(#[$m])* $v),*];
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] values_sorted const $cname:ident) => {
+        $(#[$attr])*
+        pub const $cname: [$vty; $crate::count!($([$(#[$m])*] $k)*)] = $crate::sorted_values!([$vcmp] $vty, $([$(#[$m])*] $v),*);
+    };
+    ($ctx:tt $attrs:tt entries_in_order $fname:ident) => {
+        $crate::const_map_option!($ctx $attrs entries $fname);
+    };
+    ($ctx:tt $attrs:tt entries_in_order const $cname:ident) => {
+        $crate::const_map_option!($ctx $attrs entries const $cname);
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] entries $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>() -> &'static [($kty, $vty)] where $($wc)* {
+            &$map
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] entries const $cname:ident) => {
+        $(#[$attr])*
+        pub const $cname: &'static [($kty, $vty)] = &$map;
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] iter $fname:ident) => {
+        $(#[$attr])*
+        fn $fname<$($gen),*>() -> ::core::slice::Iter<'static, ($kty, $vty)> where $($wc)* {
+            let map: &'static [($kty, $vty)] = &$map;
+            map.iter()
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] first $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>() -> Option<($kty, $vty)> where $($wc)* {
+            let pairs: &[($kty, $vty)] = &$map;
+            match pairs.first() {
+                Some(&pair) => Some(pair),
+                None => None,
+            }
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] last $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>() -> Option<($kty, $vty)> where $($wc)* {
+            let pairs: &[($kty, $vty)] = &$map;
+            match pairs.last() {
+                Some(&pair) => Some(pair),
+                None => None,
+            }
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] min_key $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>() -> $kty where $($wc)* {
+            $crate::const_assert!(
+                $crate::count!($([$(#[$m])*] $k)*) > 0,
+                concat!("const_map ", stringify!($name), " is empty, so it doesn't have a smallest key")
+            );
+            let pairs: &[($kty, $vty)] = &$map;
+            let mut key = pairs[0].0;
+            let mut i = 1;
+            while i < pairs.len() {
+                if $crate::const_lt!([$kcmp] pairs[i].0, key) {
+                    key = pairs[i].0;
+                }
+                i += 1;
+            }
+            key
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] max_key $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>() -> $kty where $($wc)* {
+            $crate::const_assert!(
+                $crate::count!($([$(#[$m])*] $k)*) > 0,
+                concat!("const_map ", stringify!($name), " is empty, so it doesn't have a largest key")
+            );
+            let pairs: &[($kty, $vty)] = &$map;
+            let mut key = pairs[0].0;
+            let mut i = 1;
+            while i < pairs.len() {
+                if $crate::const_lt!([$kcmp] key, pairs[i].0) {
+                    key = pairs[i].0;
+                }
+                i += 1;
+            }
+            key
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] floor $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(key: $kpty) -> Option<$vty> where $($wc)* {
+            let pairs: &[($kty, $vty)] = &$map;
+            let mut found: Option<usize> = None;
+            let mut i = 0;
+            while i < pairs.len() {
+                if $crate::probe_eq!([$kcmp] pairs[i].0, key) {
+                    return Some(pairs[i].1);
+                } else if $crate::probe_lt!([$kcmp] pairs[i].0, key) {
+                    found = match found {
+                        Some(j) if !$crate::const_lt!([$kcmp] pairs[j].0, pairs[i].0) => Some(j),
+                        _ => Some(i),
+                    };
+                }
+                i += 1;
+            }
+            match found {
+                Some(i) => Some(pairs[i].1),
+                None => None,
+            }
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] hashmap $fname:ident) => {
+        $crate::hashmap_fn!([$(#[$attr])*] $fname [$($gen),*] [$($wc)*], $map, $kty, $vty);
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] btreemap $fname:ident) => {
+        $crate::btreemap_fn!([$(#[$attr])*] $fname [$($gen),*] [$($wc)*], $map, $kty, $vty);
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] hashbrown $fname:ident) => {
+        $crate::hashbrown_fn!([$(#[$attr])*] $fname [$($gen),*] [$($wc)*], $map, $kty, $vty);
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] serialize $fname:ident) => {
+        $crate::serialize_fn!([$(#[$attr])*] $fname [$($gen),*] [$($wc)*], $map, $kty, $vty);
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] to_json $fname:ident) => {
+        $crate::to_json_fn!([$(#[$attr])*] $fname [$($gen),*] [$($wc)*], $map);
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] len $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>() -> usize where $($wc)* {
+            $crate::count!($([$(#[$m])*] $k)*)
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] len const $cname:ident) => {
+        $(#[$attr])*
+        pub const $cname: usize = $crate::count!($([$(#[$m])*] $k)*);
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] is_empty $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>() -> bool where $($wc)* {
+            $crate::count!($([$(#[$m])*] $k)*) == 0
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] $layout:ident $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] is_empty const $cname:ident) => {
+        $(#[$attr])*
+        pub const $cname: bool = $crate::count!($([$(#[$m])*] $k)*) == 0;
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] array_type const $tname:ident) => {
+        $(#[$attr])*
+        pub type $tname = [($kty, $vty); $crate::count!($([$(#[$m])*] $k)*)];
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] all($pred:path) $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>() -> bool where $($wc)* {
+            let mut i = 0;
+            while i < $map.len() {
+                if !$pred($map[i].0, $map[i].1) {
+                    return false;
+                }
+                i += 1;
+            }
+            true
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] any($pred:path) $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>() -> bool where $($wc)* {
+            let mut i = 0;
+            while i < $map.len() {
+                if $pred($map[i].0, $map[i].1) {
+                    return true;
+                }
+                i += 1;
+            }
+            false
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] count($pred:path) $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>() -> usize where $($wc)* {
+            let mut count = 0;
+            let mut i = 0;
+            while i < $map.len() {
+                if $pred($map[i].0, $map[i].1) {
+                    count += 1;
+                }
+                i += 1;
+            }
+            count
+        }
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] pairs $name:ident, $map:path, [$kty:ty, $kpty:ty, $vty:ty, $vpty:ty], [$($gen:tt),*] [$($wc:tt)*], { $([$(#[$m:meta])*] $k:expr => $v:expr,)* }) [$(#[$attr:meta])*] total($total:expr) $fname:ident) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>(key: $kpty) -> $vty where $($wc)* {
+            $crate::const_assert!(
+                $crate::count!($([$(#[$m])*] $k)*) == $total,
+                concat!("const_map ", stringify!($name), " doesn't have the declared total number of entries")
+            );
+            $crate::const_assert!(
+                $crate::has_all_values!([$kcmp] $kty, $total),
+                concat!("const_map ", stringify!($name), " doesn't have every value of its key type")
+            );
+            match $crate::search!([$algo $kcmp] pairs $map, $kty, $kpty, key, [$([$(#[$m])*] $k),*]) {
+                Some(i) => $map[i].1,
+                None => panic!("key not found in total const_map"),
+            }
+        }
+    };
+    // Split and interned maps only have the options which find a key's index and then use it to
+    // get the key or the value, and those which don't need the map at all.
+    (([$algo:ident $kcmp:tt $vcmp:ident] split $name:ident, $($ctx:tt)*) $attrs:tt $kind:ident $($item:tt)*) => {
+        compile_error!(concat!("const_map option `", stringify!($kind), "` can't be used with split const_map ", stringify!($name)));
+    };
+    (([$algo:ident $kcmp:tt $vcmp:ident] interned $name:ident, $($ctx:tt)*) $attrs:tt $kind:ident $($item:tt)*) => {
+        compile_error!(concat!("const_map option `", stringify!($kind), "` can't be used with interned const_map ", stringify!($name)));
+    };
+    ($ctx:tt $attrs:tt $kind:ident $(($($args:tt)*))? $fname:ident) => {
+        compile_error!(concat!("unknown const_map option `", stringify!($kind), "`"));
+    };
+    ($ctx:tt $attrs:tt $kind:ident const $cname:ident) => {
+        compile_error!(concat!("const_map option `", stringify!($kind), "` can't be a constant"));
+    };
+}
+
+/// Expands to whether a `total` map with the given number of entries has every value of its key
+/// type. This can only be checked for keys compared with `==`, which are `bool`, `char` or integers.
+/// For others, the number of entries is taken on trust.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! has_all_values {
+    ([eq] $kty:ty, $total:expr) => {
+        match <$kty as $crate::total::Values>::COUNT {
+            Some(count) => count == $total,
+            None => false,
+        }
+    };
+    ([$kcmp:tt] $kty:ty, $total:expr) => {
+        true
+    };
+}
+
+/// Expands to the key of the entry at the given index of the map, for each way the entries of a
+/// map can be laid out.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! key_at {
+    (pairs $map:path, $i:expr) => {
+        $map[$i].0
+    };
+    (split $map:path, $i:expr) => {
+        $map.keys[$i]
+    };
+    (interned $map:path, $i:expr) => {
+        $map.entries[$i].0
+    };
+}
+
+/// Expands to the value of the entry at the given index of the map, like [`key_at!`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! value_at {
+    (pairs $map:path, $i:expr) => {
+        $map[$i].1
+    };
+    (split $map:path, $i:expr) => {
+        $map.values[$i]
+    };
+    (interned $map:path, $i:expr) => {
+        $map.values[$map.entries[$i].1 as usize]
+    };
+}
+
+/// Generates the function for the `hashmap` option, if the `std` feature is enabled.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! hashmap_fn {
+    ([$(#[$attr:meta])*] $fname:ident [$($gen:tt),*] [$($wc:tt)*], $map:path, $kty:ty, $vty:ty) => {
+        $(#[$attr])*
+        fn $fname<$($gen),*>() -> $crate::collections::HashMap<$kty, $vty> where $($wc)* {
+            $map.iter().cloned().collect()
+        }
+    };
+}
+
+/// Generates the function for the `hashmap` option, if the `std` feature is enabled.
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! hashmap_fn {
+    ($($args:tt)*) => {
+        compile_error!("const_map option `hashmap` needs the `std` feature");
+    };
+}
+
+/// Generates the function for the `btreemap` option, if the `alloc` feature is enabled.
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! btreemap_fn {
+    ([$(#[$attr:meta])*] $fname:ident [$($gen:tt),*] [$($wc:tt)*], $map:path, $kty:ty, $vty:ty) => {
+        $(#[$attr])*
+        fn $fname<$($gen),*>() -> $crate::collections::BTreeMap<$kty, $vty> where $($wc)* {
+            $map.iter().cloned().collect()
+        }
+    };
+}
+
+/// Generates the function for the `btreemap` option, if the `alloc` feature is enabled.
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! btreemap_fn {
+    ($($args:tt)*) => {
+        compile_error!("const_map option `btreemap` needs the `alloc` feature");
+    };
+}
+
+/// Generates the function for the `hashbrown` option, if the `hashbrown` feature is enabled.
+#[cfg(feature = "hashbrown")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! hashbrown_fn {
+    ([$(#[$attr:meta])*] $fname:ident [$($gen:tt),*] [$($wc:tt)*], $map:path, $kty:ty, $vty:ty) => {
+        $(#[$attr])*
+        fn $fname<$($gen),*>() -> $crate::collections::HashbrownMap<$kty, $vty> where $($wc)* {
+            $map.iter().cloned().collect()
+        }
+    };
+}
+
+/// Generates the function for the `hashbrown` option, if the `hashbrown` feature is enabled.
+#[cfg(not(feature = "hashbrown"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! hashbrown_fn {
+    ($($args:tt)*) => {
+        compile_error!("const_map option `hashbrown` needs the `hashbrown` feature");
+    };
+}
+
+/// Generates the function for the `serialize` option, if the `serde` feature is enabled.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! serialize_fn {
+    ([$(#[$attr:meta])*] $fname:ident [$($gen:tt),*] [$($wc:tt)*], $map:path, $kty:ty, $vty:ty) => {
+        $(#[$attr])*
+        const fn $fname<$($gen),*>() -> $crate::Serializable<$kty, $vty> where $($wc)* {
+            $crate::Serializable(&$map)
+        }
+    };
+}
+
+/// Generates the function for the `serialize` option, if the `serde` feature is enabled.
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! serialize_fn {
+    ($($args:tt)*) => {
+        compile_error!("const_map option `serialize` needs the `serde` feature");
+    };
+}
+
+/// Generates the function for the `to_json` option, if the `json` feature is enabled.
+#[cfg(feature = "json")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! to_json_fn {
+    ([$(#[$attr:meta])*] $fname:ident [$($gen:tt),*] [$($wc:tt)*], $map:path) => {
+        $(#[$attr])*
+        fn $fname<$($gen),*>() -> $crate::collections::String where $($wc)* {
+            $crate::to_json(&$map)
+        }
+    };
+}
+
+/// Generates the function for the `to_json` option, if the `json` feature is enabled.
+#[cfg(not(feature = "json"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! to_json_fn {
+    ($($args:tt)*) => {
+        compile_error!("const_map option `to_json` needs the `json` feature");
+    };
+}
+
+/// Normalizes the entries of a map and passes them back to the `@entries` phase of
+/// [`const_map_impl!`]. With the `proc-macro` feature, this is a procedural macro which does it all
+/// at once, so that it can handle maps with any number of entries.
+#[cfg(not(feature = "proc-macro"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! normalize_entries {
+    ($krate:tt $state:tt $($entries:tt)*) => {
+        $crate::const_map_impl!(@entries $state [] [] $($entries)*);
+    };
+}
+
+/// Expands to a statement which checks a condition at compile time, even if the function it's in is
+/// never used.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! const_assert {
+    ($cond:expr, $msg:expr) => {
+        let _: [(); 0] = [(); {
+            assert!($cond, $msg);
+            0
+        }];
+    };
+}
+
+/// Expands to an expression which searches the map for a key using the given algorithm and
+/// comparison, and evaluates to `Option<usize>` with the index of the matching entry. The layout of
+/// the map comes before it, and the keys of the map are also given, for algorithms which need them
+/// as tokens rather than as the map.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! search {
+    // Interned maps are searched through their entries, which pair each key with the index of its
+    // value.
+    ([$algo:ident $cmp:tt] interned $map:path, $($rest:tt)*) => {
+        $crate::search!([$algo $cmp] pairs $map.entries, $($rest)*)
+    };
+    // For split maps, only the keys are searched.
+    ([linear $cmp:tt] split $map:path, $kty:ty, $kpty:ty, $key:expr, $keys:tt) => {{
+        const fn find(keys: &[$kty], key: $kpty) -> Option<usize> {
+            let mut i = 0;
+            while i < keys.len() {
+                if $crate::probe_eq!([$cmp] keys[i], key) {
+                    return Some(i);
+                }
+                i += 1;
+            }
+            None
+        }
+        find(&$map.keys, $key)
+    }};
+    ([binary $cmp:tt] split $map:path, $kty:ty, $kpty:ty, $key:expr, $keys:tt) => {{
+        const fn find(keys: &[$kty], key: $kpty) -> Option<usize> {
+            let mut lo = 0;
+            let mut hi = keys.len();
+            while lo < hi {
+                let mid = lo + (hi - lo) / 2;
+                if $crate::probe_eq!([$cmp] keys[mid], key) {
+                    return Some(mid);
+                } else if $crate::probe_lt!([$cmp] keys[mid], key) {
+                    lo = mid + 1;
+                } else {
+                    hi = mid;
+                }
+            }
+            None
+        }
+        find(&$map.keys, $key)
+    }};
+    ([linear $cmp:tt] pairs $map:expr, $kty:ty, $kpty:ty, $key:expr, $keys:tt) => {{
+        const fn find<V>(pairs: &[($kty, V)], key: $kpty) -> Option<usize> {
+            let mut i = 0;
+            while i < pairs.len() {
+                if $crate::probe_eq!([$cmp] pairs[i].0, key) {
+                    return Some(i);
+                }
+                i += 1;
+            }
+            None
+        }
+        find(&$map, $key)
+    }};
+    ([binary $cmp:tt] pairs $map:expr, $kty:ty, $kpty:ty, $key:expr, $keys:tt) => {{
+        const fn find<V>(pairs: &[($kty, V)], key: $kpty) -> Option<usize> {
+            let mut lo = 0;
+            let mut hi = pairs.len();
+            while lo < hi {
+                let mid = lo + (hi - lo) / 2;
+                if $crate::probe_eq!([$cmp] pairs[mid].0, key) {
+                    return Some(mid);
+                } else if $crate::probe_lt!([$cmp] pairs[mid].0, key) {
+                    lo = mid + 1;
+                } else {
+                    hi = mid;
+                }
+            }
+            None
+        }
+        find(&$map, $key)
+    }};
+    // The table is built in an inline constant in each function which searches the map, rather than
+    // once in an associated constant next to it, since a `macro_rules!` macro can't make up a name
+    // for one which won't clash with the other items of the `impl` block. Identical constants are
+    // only emitted once, so the binary still has just one copy of the table.
+    ([hash $cmp:tt] pairs $map:expr, $kty:ty, $kpty:ty, $key:expr, $keys:tt) => {{
+        const fn build<V, const N: usize>(pairs: &[($kty, V); N]) -> $crate::hash::Table<N> {
+            let mut hashes = [0; N];
+            let mut i = 0;
+            while i < N {
+                hashes[i] = $crate::const_hash!([$cmp] pairs[i].0);
+                i += 1;
+            }
+            $crate::hash::Table::new(hashes)
+        }
+        const fn find<V, const N: usize>(
+            pairs: &[($kty, V); N],
+            table: &$crate::hash::Table<N>,
+            key: $kpty,
+        ) -> Option<usize> {
+            match table.get($crate::const_hash!([$cmp] key)) {
+                Some(i) if $crate::const_eq!([$cmp] pairs[i].0, key) => Some(i),
+                _ => None,
+            }
+        }
+        find(&$map, &const { build(&$map) }, $key)
+    }};
+    // The hashes of the keys are worked out at compile time, so that only keys with the same hash
+    // need to be compared. Like the table of a `hashed` map, they're worked out in each function
+    // which searches the map, and the binary has one copy of them.
+    ([fnv $cmp:tt] pairs $map:expr, $kty:ty, $kpty:ty, $key:expr, $keys:tt) => {{
+        const fn hashes<V, const N: usize>(pairs: &[($kty, V); N]) -> [u64; N] {
+            let mut hashes = [0; N];
+            let mut i = 0;
+            while i < N {
+                hashes[i] = $crate::const_hash!([$cmp] pairs[i].0);
+                i += 1;
+            }
+            hashes
+        }
+        const fn find<V, const N: usize>(
+            pairs: &[($kty, V); N],
+            hashes: &[u64; N],
+            key: $kpty,
+        ) -> Option<usize> {
+            let hash = $crate::const_hash!([$cmp] key);
+            let mut i = 0;
+            while i < N {
+                if hashes[i] == hash && $crate::const_eq!([$cmp] pairs[i].0, key) {
+                    return Some(i);
+                }
+                i += 1;
+            }
+            None
+        }
+        find(&$map, &const { hashes(&$map) }, $key)
+    }};
+    ([jump $cmp:tt] pairs $map:expr, $kty:ty, $kpty:ty, $key:expr, $keys:tt) => {{
+        const fn find<V>(pairs: &[($kty, V)], key: $kpty) -> Option<usize> {
+            if pairs.is_empty() {
+                return None;
+            }
+            // Working with `u128` covers every key type. Keys before the first one wrap around to
+            // a big offset, which is out of range like those after the last one.
+            let offset = (key as u128).wrapping_sub(pairs[0].0 as u128);
+            if offset < pairs.len() as u128 {
+                Some(offset as usize)
+            } else {
+                None
+            }
+        }
+        find(&$map, $key)
+    }};
+    ([match $cmp:tt] pairs $map:expr, $kty:ty, $kpty:ty, $key:expr, [$([$(#[$m:meta])*] $k:expr),*]) => {{
+        // The index of each key is worked out at compile time rather than counted here, so that
+        // entries left out by `#[cfg]` don't throw it off.
+        // It isn't used at all if the map is empty.
+        #[allow(dead_code)]
+        const fn index<V>(pairs: &[($kty, V)], key: $kty) -> usize {
+            let mut i = 0;
+            while !$crate::const_eq!([$cmp] pairs[i].0, key) {
+                i += 1;
+            }
+            i
+        }
+        let found: Option<usize> = match $key {
+            $($(#[$m])* $k => Some(const { index(&$map, $k) }),)*
+            _ => None,
+        };
+        found
+    }};
+}
+
+/// Expands to an expression which searches the values of the map, evaluating to `Option<usize>`
+/// with the index of the first entry with the given value.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! search_values {
+    ([$cmp:tt] sorted $map:expr, $vty:ty, $vpty:ty, $value:expr) => {{
+        const fn find<K>(pairs: &[(K, $vty)], value: $vpty) -> Option<usize> {
+            let mut lo = 0;
+            let mut hi = pairs.len();
+            while lo < hi {
+                let mid = lo + (hi - lo) / 2;
+                if $crate::const_eq!([$cmp] pairs[mid].1, value) {
+                    return Some(mid);
+                } else if $crate::const_lt!([$cmp] pairs[mid].1, value) {
+                    lo = mid + 1;
+                } else {
+                    hi = mid;
+                }
+            }
+            None
+        }
+        find($map, $value)
+    }};
+    ([$cmp:tt] $map:expr, $vty:ty, $vpty:ty, $value:expr) => {{
+        const fn find<K>(pairs: &[(K, $vty)], value: $vpty) -> Option<usize> {
+            let mut i = 0;
+            while i < pairs.len() {
+                if $crate::const_eq!([$cmp] pairs[i].1, value) {
+                    return Some(i);
+                }
+                i += 1;
+            }
+            None
+        }
+        find($map, $value)
+    }};
+}
+
+/// Expands to an expression hashing a key to a `u64` in a const context. Different keys should
+/// have different hashes, though they don't need to be well distributed.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! const_hash {
+    ([eq] $k:expr) => {
+        $crate::hash::hash_wide($k as u128)
+    };
+    ([enum] $k:expr) => {
+        $k as i128 as u64
+    };
+    ([f32] $k:expr) => {
+        $k.to_bits() as u64
+    };
+    ([f64] $k:expr) => {
+        $k.to_bits()
+    };
+    ([str] $k:expr) => {
+        $crate::hash::hash_str($k)
+    };
+    ([ci] $k:expr) => {
+        $crate::hash::hash_str_ignore_case($k)
+    };
+    ([bytes] $k:expr) => {
+        $crate::hash::hash_bytes($k)
+    };
+}
+
+/// Expands to an expression comparing two keys or values for equality in a const context.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! const_eq {
+    ([eq] $a:expr, $b:expr) => {
+        $crate::partial_eq!($a, $b)
+    };
+    ([str] $a:expr, $b:expr) => {
+        $crate::cmp::str_eq($a, $b)
+    };
+    ([ci] $a:expr, $b:expr) => {
+        $crate::cmp::str_eq_ignore_case($a, $b)
+    };
+    ([bytes] $a:expr, $b:expr) => {
+        $crate::cmp::bytes_eq($a, $b)
+    };
+    ([enum] $a:expr, $b:expr) => {
+        ($a as i128) == ($b as i128)
+    };
+    ([f32] $a:expr, $b:expr) => {
+        $crate::cmp::f32_eq($a, $b)
+    };
+    ([f64] $a:expr, $b:expr) => {
+        $crate::cmp::f64_eq($a, $b)
+    };
+    ([(using $eq:path)] $a:expr, $b:expr) => {
+        $eq(&$a, &$b)
+    };
+    ([(ref $cmp:tt)] $a:expr, $b:expr) => {
+        $crate::const_eq!([$cmp] $a, $b)
+    };
+    ([(tuple $($i:tt)*)] $a:expr, $b:expr) => {
+        true $(&& $crate::partial_eq!($a.$i, $b.$i))*
+    };
+    // Ranges count as equal if they overlap, so that keys which overlap aren't unique.
+    ([(range $cmp:tt)] $a:expr, $b:expr) => {
+        !$crate::const_lt!([$cmp] *$a.end(), *$b.start()) && !$crate::const_lt!([$cmp] *$b.end(), *$a.start())
+    };
+}
+
+/// Expands to an expression checking whether two keys are equal with `==`, for types which don't
+/// need a special comparison.
+#[cfg(not(feature = "const-trait"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! partial_eq {
+    ($a:expr, $b:expr) => {
+        $a == $b
+    };
+}
+
+/// Expands to an expression checking whether two keys are equal with their const `PartialEq`
+/// implementation, so that any type implementing it can be used.
+#[cfg(feature = "const-trait")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! partial_eq {
+    ($a:expr, $b:expr) => {
+        $crate::cmp::partial_eq(&$a, &$b)
+    };
+}
+
+/// Expands to an expression checking whether one key is less than another in a const context.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! const_lt {
+    ([eq] $a:expr, $b:expr) => {
+        $a < $b
+    };
+    ([str] $a:expr, $b:expr) => {
+        $crate::cmp::str_lt($a, $b)
+    };
+    ([ci] $a:expr, $b:expr) => {
+        $crate::cmp::str_lt_ignore_case($a, $b)
+    };
+    ([bytes] $a:expr, $b:expr) => {
+        $crate::cmp::bytes_lt($a, $b)
+    };
+    ([enum] $a:expr, $b:expr) => {
+        ($a as i128) < ($b as i128)
+    };
+    ([f32] $a:expr, $b:expr) => {
+        $crate::cmp::f32_lt($a, $b)
+    };
+    ([f64] $a:expr, $b:expr) => {
+        $crate::cmp::f64_lt($a, $b)
+    };
+    ([(ref $cmp:tt)] $a:expr, $b:expr) => {
+        $crate::const_lt!([$cmp] $a, $b)
+    };
+    ([(tuple $($i:tt)*)] $a:expr, $b:expr) => {
+        $crate::tuple_lt!($a, $b, $($i)*)
+    };
+    // One range is less than another if it ends before the other starts.
+    ([(range $cmp:tt)] $a:expr, $b:expr) => {
+        $crate::const_lt!([$cmp] *$a.end(), *$b.start())
+    };
+}
+
+/// Expands to an expression checking whether one tuple is less than another in a const context,
+/// comparing the elements with the given indices in order.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! tuple_lt {
+    ($a:expr, $b:expr, $i:tt) => {
+        $a.$i < $b.$i
+    };
+    ($a:expr, $b:expr, $i:tt $($rest:tt)+) => {
+        $a.$i < $b.$i || ($crate::partial_eq!($a.$i, $b.$i) && $crate::tuple_lt!($a, $b, $($rest)+))
+    };
+}
+
+/// Like [`const_eq!`], but comparing a key in the map with a key passed to a lookup function, which
+/// is a reference if the map's keys are passed by reference.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! probe_eq {
+    ([(ref $cmp:tt)] $k:expr, $probe:expr) => {
+        $crate::const_eq!([$cmp] $k, *$probe)
+    };
+    ([(range $cmp:tt)] $k:expr, $probe:expr) => {
+        !$crate::const_lt!([$cmp] $probe, *$k.start()) && !$crate::const_lt!([$cmp] *$k.end(), $probe)
+    };
+    ([$cmp:tt] $k:expr, $probe:expr) => {
+        $crate::const_eq!([$cmp] $k, $probe)
+    };
+}
+
+/// Like [`const_lt!`], but checking whether a key in the map is less than a key passed to a lookup
+/// function, in the same way as [`probe_eq!`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! probe_lt {
+    ([(ref $cmp:tt)] $k:expr, $probe:expr) => {
+        $crate::const_lt!([$cmp] $k, *$probe)
+    };
+    ([(range $cmp:tt)] $k:expr, $probe:expr) => {
+        $crate::const_lt!([$cmp] *$k.end(), $probe)
+    };
+    ([$cmp:tt] $k:expr, $probe:expr) => {
+        $crate::const_lt!([$cmp] $k, $probe)
+    };
+}
+
+/// Expands to the given number of entries, after checking at compile time that it's the number
+/// the map was declared to have, if any.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! expect_len {
+    ($name:ident, $len:expr) => {
+        $len
+    };
+    ($name:ident, $len:expr, $expected:expr) => {{
+        let len = $len;
+        if len != $expected {
+            panic!(concat!("const_map ", stringify!($name), " doesn't have the declared number of entries"));
+        }
+        len
+    }};
+}
+
+/// Expands to the number of keys given, after checking them at compile time, either that they're
+/// unique (or for range keys, that they don't overlap), that they're sorted, or that they're a
+/// range of consecutive integers. The keys of hashed maps are checked to be unique by their hashes.
+/// The keys of multi maps aren't checked at all.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! checked_len {
+    ([unique (range $cmp:tt)] $name:ident, $kty:ty, $([$(#[$m:meta])*] $k:expr),*) => {{
+        if !$crate::all_unique!([(range $cmp)] $kty, $([$(#[$m])*] $k),*) {
+            panic!(concat!("overlapping keys in const_map ", stringify!($name)));
+        }
+        $crate::count!($([$(#[$m])*] $k)*)
+    }};
+    ([unique $cmp:tt] $($rest:tt)*) => {
+        $crate::unique_len!([$cmp] $($rest)*)
+    };
+    ([sorted $cmp:tt] $($rest:tt)*) => {
+        $crate::sorted_len!([$cmp] $($rest)*)
+    };
+    ([hashed $cmp:tt] $($rest:tt)*) => {
+        $crate::hashed_len!([$cmp] $($rest)*)
+    };
+    ([sorted_hashed $cmp:tt] $($rest:tt)*) => {{
+        $crate::hashed_len!([$cmp] $($rest)*);
+        $crate::sorted_len!([$cmp] $($rest)*)
+    }};
+    ([dense $cmp:tt] $($rest:tt)*) => {
+        $crate::dense_len!([$cmp] $($rest)*)
+    };
+    ([any $cmp:tt] $name:ident, $kty:ty, $([$(#[$m:meta])*] $k:expr),*) => {
+        $crate::count!($([$(#[$m])*] $k)*)
+    };
+}
+
+/// Expands to the given number of entries, after checking at compile time that each of the required
+/// keys given would be found by a lookup.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! required_len {
+    ([$cmp:tt] $name:ident, $kty:ty, $kpty:ty, $keys:tt, [], $len:expr) => {
+        $len
+    };
+    ([$cmp:tt] $name:ident, $kty:ty, $kpty:ty, [$([$(#[$m:meta])*] $k:expr),*], [$($req:expr),*], $len:expr) => {{
+        let keys: &[$kty] = &[$($(#[$m])* $k),*];
+        $(
+            let probe: $kpty = $req;
+            let mut i = 0;
+            while i < keys.len() && !$crate::probe_eq!([$cmp] keys[i], probe) {
+                i += 1;
+            }
+            if i == keys.len() {
+                panic!(concat!("const_map ", stringify!($name), " doesn't have the required key ", stringify!($req)));
+            }
+        )*
+        $len
+    }};
+}
+
+/// Expands to the given number of entries, after checking at compile time that the entries given
+/// pass each of the validators from the `validate` option.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! validated_len {
+    ($name:ident, [$kty:ty, $vty:ty], $entries:tt, [], $len:expr) => {
+        $len
+    };
+    ($name:ident, [$kty:ty, $vty:ty], [$([$(#[$m:meta])*] $entry:expr),*], [$($valid:path),*], $len:expr) => {{
+        let entries: &[($kty, $vty)] = &[$($(#[$m])* $entry),*];
+        $(
+            if !$valid(entries) {
+                panic!(concat!("const_map ", stringify!($name), " doesn't pass the validator ", stringify!($valid)));
+            }
+        )*
+        $len
+    }};
+}
+
+/// Expands to the given number of entries, after checking at compile time that the values given
+/// are unique, if the map was declared with the `unique_values` option.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! checked_values {
+    ([] [$cmp:tt] $name:ident, $vty:ty, $values:tt, $len:expr) => {
+        $len
+    };
+    ([unique] [$cmp:tt] $name:ident, $vty:ty, [$([$(#[$m:meta])*] $v:expr),*], $len:expr) => {{
+        if !$crate::all_unique!([$cmp] $vty, $([$(#[$m])*] $v),*) {
+            panic!(concat!("duplicate value in const_map ", stringify!($name)));
+        }
+        $len
+    }};
+}
+
+/// Expands to the number of keys given, after checking at compile time that no two of them are
+/// equal.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! unique_len {
+    ([$cmp:tt] $name:ident, $kty:ty, $([$(#[$m:meta])*] $k:expr),*) => {{
+        if !$crate::all_unique!([$cmp] $kty, $([$(#[$m])*] $k),*) {
+            panic!(concat!("duplicate key in const_map ", stringify!($name)));
+        }
+        $crate::count!($([$(#[$m])*] $k)*)
+    }};
+}
+
+/// Expands to an expression checking in a const context that no two of the items given are equal.
+///
+/// This is done by sorting them and comparing neighbours, which is much quicker than comparing
+/// every pair for a big map. That can't be done for custom comparison functions, which don't say
+/// how to sort the items, nor for ranges, which can overlap without being next to each other, nor
+/// for types compared with their const `PartialEq` implementation, which might not support `<`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! all_unique {
+    ([(using $eq:path)] $($rest:tt)*) => {
+        $crate::all_unique!(@pairs [(using $eq)] $($rest)*)
+    };
+    ([(ref (using $eq:path))] $($rest:tt)*) => {
+        $crate::all_unique!(@pairs [(ref (using $eq))] $($rest)*)
+    };
+    ([(range $cmp:tt)] $($rest:tt)*) => {
+        $crate::all_unique!(@pairs [(range $cmp)] $($rest)*)
+    };
+    ([eq] $($rest:tt)*) => {
+        $crate::all_unique_eq!($($rest)*)
+    };
+    (@pairs [$cmp:tt] $ty:ty, $([$(#[$m:meta])*] $x:expr),*) => {{
+        let items: &[$ty] = &[$($(#[$m])* $x),*];
+        let mut unique = true;
+        let mut i = 0;
+        while unique && i < items.len() {
+            let mut j = i + 1;
+            while unique && j < items.len() {
+                unique = !$crate::const_eq!([$cmp] items[i], items[j]);
+                j += 1;
+            }
+            i += 1;
+        }
+        unique
+    }};
+    (@sorted [$cmp:tt] $ty:ty, $([$(#[$m:meta])*] $x:expr),*) => {{
+        let items: &[$ty] = &[$($(#[$m])* $x),*];
+        let order: &mut [usize] = &mut [0; $crate::count!($([$(#[$m])*] $x)*)];
+        let mut i = 0;
+        while i < order.len() {
+            order[i] = i;
+            i += 1;
+        }
+        // Heapsort the indices of the items, first building the heap and then taking the top off
+        // it until it's empty, sifting down an entry each time.
+        let mut start = order.len() / 2;
+        let mut end = order.len();
+        loop {
+            let mut root = if start > 0 {
+                start -= 1;
+                start
+            } else if end > 1 {
+                end -= 1;
+                let top = order[0];
+                order[0] = order[end];
+                order[end] = top;
+                0
+            } else {
+                break;
+            };
+            loop {
+                let mut child = 2 * root + 1;
+                if child >= end {
+                    break;
+                }
+                if child + 1 < end && $crate::const_lt!([$cmp] items[order[child]], items[order[child + 1]]) {
+                    child += 1;
+                }
+                if !$crate::const_lt!([$cmp] items[order[root]], items[order[child]]) {
+                    break;
+                }
+                let parent = order[root];
+                order[root] = order[child];
+                order[child] = parent;
+                root = child;
+            }
+        }
+        let mut unique = true;
+        let mut i = 1;
+        while unique && i < order.len() {
+            unique = !$crate::const_eq!([$cmp] items[order[i - 1]], items[order[i]]);
+            i += 1;
+        }
+        unique
+    }};
+    ($($rest:tt)*) => {
+        $crate::all_unique!(@sorted $($rest)*)
+    };
+}
+
+/// Like [`all_unique!`] for types which don't need a special comparison, which are sorted with `<`.
+#[cfg(not(feature = "const-trait"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! all_unique_eq {
+    ($($rest:tt)*) => {
+        $crate::all_unique!(@sorted [eq] $($rest)*)
+    };
+}
+
+/// Like [`all_unique!`] for types which don't need a special comparison, which are compared with
+/// their const `PartialEq` implementation and so can't be sorted.
+#[cfg(feature = "const-trait")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! all_unique_eq {
+    ($($rest:tt)*) => {
+        $crate::all_unique!(@pairs [eq] $($rest)*)
+    };
+}
+
+/// Like [`unique_len!`], but checking the keys' hashes are all different instead, which is enough
+/// to show the keys are too, and much quicker for a big map. Only if they aren't are the keys
+/// compared, to find out why.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! hashed_len {
+    ([$cmp:tt] $name:ident, $kty:ty, $([$(#[$m:meta])*] $k:expr),*) => {{
+        let keys: &[$kty] = &[$($(#[$m])* $k),*];
+        let mut hashes = [0; $crate::count!($([$(#[$m])*] $k)*)];
+        let mut i = 0;
+        while i < keys.len() {
+            hashes[i] = $crate::const_hash!([$cmp] keys[i]);
+            i += 1;
+        }
+        if !$crate::hash::all_distinct(hashes) {
+            if !$crate::all_unique!([$cmp] $kty, $([$(#[$m])*] $k),*) {
+                panic!(concat!("duplicate key in const_map ", stringify!($name)));
+            }
+            let names: &[&str] = &[$($(#[$m])* stringify!($k)),*];
+            let mut i = 0;
+            while i < names.len() {
+                let mut j = i + 1;
+                while j < names.len() {
+                    if hashes[i] == hashes[j] {
+                        $crate::hash::collision(stringify!($name), names[i], names[j]);
+                    }
+                    j += 1;
+                }
+                i += 1;
+            }
+        }
+        $crate::count!($([$(#[$m])*] $k)*)
+    }};
+}
+
+/// Expands to the number of keys given, after checking at compile time that they are in strictly
+/// increasing order (which also means there are no duplicates).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! sorted_len {
+    ([$cmp:tt] $name:ident, $kty:ty, $([$(#[$m:meta])*] $k:expr),*) => {{
+        let keys: &[$kty] = &[$($(#[$m])* $k),*];
+        let mut i = 1;
+        while i < keys.len() {
+            if !$crate::const_lt!([$cmp] keys[i - 1], keys[i]) {
+                panic!(concat!("keys of const_map ", stringify!($name), " are out of order"));
+            }
+            i += 1;
+        }
+        $crate::count!($([$(#[$m])*] $k)*)
+    }};
+}
+
+/// Expands to an expression checking in a const context that the values given are in strictly
+/// increasing order.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! values_sorted {
+    ([$cmp:tt] $vty:ty, $([$(#[$m:meta])*] $v:expr),*) => {{
+        let values: &[$vty] = &[$($(#[$m])* $v),*];
+        let mut sorted = true;
+        let mut i = 1;
+        while sorted && i < values.len() {
+            sorted = $crate::const_lt!([$cmp] values[i - 1], values[i]);
+            i += 1;
+        }
+        sorted
+    }};
+}
+
+/// Expands to an array of the values given, sorted in ascending order at compile time, or by the
+/// given field of each value, in which case values with equal fields stay in the same order.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! sorted_values {
+    ([$cmp:tt] $vty:ty, $([$(#[$m:meta])*] $v:expr),*) => {
+        $crate::sorted_values!([$cmp] $vty, $([$(#[$m])*] $v),* =>)
+    };
+    ([$cmp:tt] $vty:ty, $([$(#[$m:meta])*] $v:expr),* => $($field:tt)*) => {{
+        let mut values: [$vty; $crate::count!($([$(#[$m])*] $v)*)] = [$($(#[$m])* $v),*];
+        // Insertion sort, which is simple, needs no extra space, and keeps equal values in order.
+        let mut i = 1;
+        while i < values.len() {
+            let mut j = i;
+            while j > 0 && $crate::const_lt!([$cmp] values[j]$($field)*, values[j - 1]$($field)*) {
+                let value = values[j];
+                values[j] = values[j - 1];
+                values[j - 1] = value;
+                j -= 1;
+            }
+            i += 1;
+        }
+        values
+    }};
+}
+
+/// Expands to the number of distinct values given, working it out at compile time.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! distinct_count {
+    ([$cmp:tt] $vty:ty, $([$(#[$m:meta])*] $v:expr),*) => {{
+        let values: &[$vty] = &[$($(#[$m])* $v),*];
+        let mut count = 0;
+        let mut i = 0;
+        while i < values.len() {
+            let mut j = 0;
+            while j < i && !$crate::const_eq!([$cmp] values[j], values[i]) {
+                j += 1;
+            }
+            if j == i {
+                count += 1;
+            }
+            i += 1;
+        }
+        count
+    }};
+}
+
+/// Expands to an [`Interned`] holding the entries given, with each distinct value stored once.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! interned {
+    ([$cmp:tt] $kty:ty, $vty:ty, $([$(#[$m:meta])*] $k:expr => $v:expr),*) => {{
+        let mut entries: [($kty, u32); $crate::count!($([$(#[$m])*] $k)*)] = [$($(#[$m])* ($k, 0)),*];
+        let mut values: [$vty; $crate::count!($([$(#[$m])*] $k)*)] = [$($(#[$m])* $v),*];
+        // Move each value which hasn't been seen before down to the end of the distinct ones.
+        let mut distinct = 0;
+        let mut i = 0;
+        while i < values.len() {
+            let mut j = 0;
+            while j < distinct && !$crate::const_eq!([$cmp] values[j], values[i]) {
+                j += 1;
+            }
+            if j == distinct {
+                values[distinct] = values[i];
+                distinct += 1;
+            }
+            entries[i].1 = j as u32;
+            i += 1;
+        }
+        $crate::Interned {
+            entries,
+            values: match values.first_chunk() {
+                Some(values) => *values,
+                None => unreachable!(),
+            },
+        }
+    }};
+}
+
+/// Expands to the number of keys given, after checking at compile time that each of them is one
+/// more than the one before.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! dense_len {
+    ([$cmp:tt] $name:ident, $kty:ty, $([$(#[$m:meta])*] $k:expr),*) => {{
+        let keys: &[$kty] = &[$($(#[$m])* $k),*];
+        let mut i = 1;
+        while i < keys.len() {
+            // Comparing them too rules out wrapping around from the largest key to the smallest.
+            let next = (keys[i - 1] as u128).wrapping_add(1);
+            if keys[i] as u128 != next || !$crate::const_lt!([$cmp] keys[i - 1], keys[i]) {
+                panic!(concat!("keys of jump const_map ", stringify!($name), " aren't consecutive"));
+            }
+            i += 1;
+        }
+        $crate::count!($([$(#[$m])*] $k)*)
+    }};
+}
+
+/// Expands to the number of items given, each preceded by its attributes in brackets, and
+/// excluding any which are disabled by `#[cfg]` attributes.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! count {
+    ($([$(#[$attr:meta])*] $x:tt)*) => {
+        <[()]>::len(&[$($(#[$attr])* ()),*])
+    };
+}
+
+#[cfg(test)]
+mod test {
+    struct Fruits;
+
+    impl Fruits {
+        const_map!(MAP, map_get(), (char => &'static str) {
+            'a' => "apple",
+            'b' => "banana",
+            'c' => "clementine",
+            'd' => "durian",
+        }, expect: expect(), must: must(), get_ref: get_ref(), first: first(), last: last(), is_empty: is_empty());
+    }
+
+    impl_index!(struct FruitIndex, Fruits::get_ref, (char => &'static str));
+
+    #[test]
+    fn test() {
+        assert_eq!(Fruits::map_get('b'), Some("banana"));
+        assert_eq!(Fruits::map_get('x'), None);
+    }
+
+    pub struct FruitOf<const TAG: char>;
+
+    impl<const TAG: char> FruitOf<TAG> {
+        pub const FRUIT: &'static str = match Fruits::map_get(TAG) {
+            Some(s) => s,
+            None => panic!("no fruit found"),
+        };
+    }
+
+    #[test]
+    fn test_generic_const() {
+        assert_eq!(FruitOf::<'d'>::FRUIT, "durian");
+    }
+
+    pub struct ExpectedFruit<const TAG: char>;
+
+    impl<const TAG: char> ExpectedFruit<TAG> {
+        pub const FRUIT: &'static str = Fruits::expect(TAG, "no fruit found");
+        pub const MUST: &'static str = Fruits::must(TAG);
+    }
+
+    #[test]
+    fn test_expect() {
+        assert_eq!(ExpectedFruit::<'a'>::FRUIT, "apple");
+        assert_eq!(ExpectedFruit::<'b'>::MUST, "banana");
+        assert_eq!(Fruits::map_get('c'), Some("clementine"));
+    }
+
+    #[test]
+    fn test_index() {
+        assert_eq!(FruitIndex['c'], "clementine");
+    }
+
+    #[test]
+    #[should_panic(expected = "key not found")]
+    fn test_index_missing() {
+        let _ = FruitIndex['z'];
+    }
+
+    struct Empty;
+
+    impl Empty {
+        const_map!(EMPTY, get(), rget(), (u8 => u8) {}, first: first(), last: last(), is_empty: is_empty(),
+            is_empty: IS_EMPTY, len: LEN, contains_key: has(), keys: keys(), values_sorted: values_sorted(), entries: entries(), floor: floor(),
+            all(Self::never): all_never(), any(Self::never): any_never(), count(Self::never): count_never());
+        const_map!(sorted SORTED, get_sorted(), (&'static str => u8) {}, position: position());
+        const_map!(hashed HASHED, get_hashed(), (u32 => u8) {});
+        const_map!(jump JUMP, get_jump(), (u8 => u8) {});
+        const_map!(match MATCH, get_match(), (u8 => u8) {});
+        const_map!(split SPLIT, get_split(), (u8 => u8) {}, is_empty: split_is_empty());
+
+        const fn never(_: u8, _: u8) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn test_first_last() {
+        assert_eq!(Fruits::first(), Some(('a', "apple")));
+        assert_eq!(Fruits::last(), Some(('d', "durian")));
+        assert_eq!(Empty::first(), None);
+        assert_eq!(Empty::last(), None);
+        assert_eq!(Empty::get(0), None);
+    }
+
+    #[test]
+    fn test_empty() {
+        const { assert!(Empty::is_empty()) };
+        assert_eq!(Empty::IS_EMPTY, Empty::is_empty());
+        assert!(!Fruits::is_empty());
+        assert_eq!(Empty::LEN, 0);
+        assert_eq!(Empty::rget(0), None);
+        assert!(!Empty::has(0));
+        assert_eq!(Empty::keys(), [0u8; 0]);
+        assert!(Empty::entries().is_empty());
+        assert_eq!(Empty::values_sorted(), [0u8; 0]);
+        assert_eq!(Empty::floor(u8::MAX), None);
+        assert!(Empty::all_never());
+        assert!(!Empty::any_never());
+        assert_eq!(Empty::count_never(), 0);
+        assert_eq!(Empty::get_sorted(""), None);
+        assert_eq!(Empty::position(""), None);
+        assert_eq!(Empty::get_hashed(0), None);
+        assert_eq!(Empty::get_jump(0), None);
+        assert_eq!(Empty::get_match(0), None);
+        assert_eq!(Empty::get_split(0), None);
+        assert!(Empty::split_is_empty());
+    }
+
+    struct Borrowed<'a>(core::marker::PhantomData<&'a str>);
+
+    impl<'a> Borrowed<'a> {
+        const_map!(MAP, get(), (u8 => &'a str) {
+            1 => "one",
+            2 => "two",
+        }, get_or: get_or(), get_ref: get_ref());
+
+        const_map!(split SPLIT, get_split(), (u8 => &'a str) {
+            1 => "uno",
+        });
+
+        const_map!(sorted NAMES, number(), (&'a str => (u8, &'a str)) {
+            "one" => (1, "uno"),
+            "two" => (2, "dos"),
+        }, contains_key: is_number());
+    }
+
+    struct Generic<T>(core::marker::PhantomData<T>);
+
+    impl<T> Generic<T> {
+        // Copying the values out of the map needs `T: Copy`, which the `impl` block doesn't have.
+        const_map!(MAP, get() where T: Copy, (u8 => Option<T>) {
+            0 => None,
+        }, get_or: get_or(), contains_key: has());
+
+        const_map!(sorted NAMES, name<'b>() where T: 'b, (u8 => &'static str) {
+            1 => "one",
+            2 => "two",
+        }, position: position());
+    }
+
+    #[test]
+    fn test_generic_impl() {
+        fn name(n: u8, default: &str) -> &str {
+            Borrowed::get_or(n, default)
+        }
+        let bytes = *b"many";
+        let default = core::str::from_utf8(&bytes).unwrap();
+        assert_eq!(name(1, default), "one");
+        assert_eq!(name(3, default), "many");
+        assert_eq!(Borrowed::get_ref(2), Some(&"two"));
+        assert_eq!(Borrowed::get(0), None);
+        assert_eq!(Borrowed::get_split(1), Some("uno"));
+        let bytes = *b"two";
+        assert_eq!(Borrowed::number(core::str::from_utf8(&bytes).unwrap()), Some((2, "dos")));
+        assert!(!Borrowed::is_number("three"));
+        assert_eq!(Generic::<u8>::get(0), Some(None));
+        assert_eq!(Generic::<u8>::get_or(1, Some(1)), Some(1));
+        assert!(Generic::<u8>::has(0));
+        assert_eq!(Generic::<core::cell::Cell<u8>>::name(2), Some("two"));
+        assert_eq!(Generic::<core::cell::Cell<u8>>::position(1), Some(0));
+    }
+
+    struct ReverseSorted;
+
+    impl ReverseSorted {
+        const_map!(bijective sorted MAP, get(), sorted letter(), (u8 => char) {
+            1 => 'a',
+            2 => 'b',
+            3 => 'c',
+            #[cfg(any())]
+            4 => 'a',
+        });
+
+        const_map!(NAMES, name(), (u8 => &'static str) {
+            3 => "apple",
+            1 => "banana",
+            2 => "clementine",
+        }, reverse_sorted: number());
+    }
+
+    #[test]
+    fn test_reverse_sorted() {
+        const B: Option<u8> = ReverseSorted::letter('b');
+        assert_eq!(B, Some(2));
+        assert_eq!(ReverseSorted::letter('d'), None);
+        assert_eq!(ReverseSorted::get(3), Some('c'));
+        assert_eq!(ReverseSorted::number("apple"), Some(3));
+        assert_eq!(ReverseSorted::number("clementine"), Some(2));
+        assert_eq!(ReverseSorted::number("durian"), None);
+        assert_eq!(ReverseSorted::name(1), Some("banana"));
+    }
+
+    struct FnAttributes;
+
+    impl FnAttributes {
+        // If the attributes weren't put on the functions, the unused ones would be warned about.
+        const_map!(MAP, #[inline(always)] get(), #[allow(dead_code)] rget(), (u8 => char) {
+            1 => 'a',
+        }, #[allow(dead_code)] #[must_use] contains_key: has(), #[allow(dead_code)] len: LEN);
+    }
+
+    #[test]
+    fn test_fn_attributes() {
+        assert_eq!(FnAttributes::get(1), Some('a'));
+    }
+
+    const fn not_empty<K, V>(entries: &[(K, V)]) -> bool {
+        !entries.is_empty()
+    }
+
+    struct SplitMaps;
+
+    impl SplitMaps {
+        const_map!(split MAP, get(), (&'static str => u64) {
+            "a" => 1,
+            #[cfg(any())]
+            "b" => 2,
+            "c" => 3,
+        }, contains_key: has(), try_get: try_get(), get_key_value: get_key_value(), from_str: number_from_str(), len: LEN, require("c"),
+            validate(not_empty));
+
+        const_map!(sorted split SORTED[3], get_sorted(), (u16 => u64) {
+            10 => 100,
+            20 => 200,
+            30 => 300,
+        }, unique_values, position: position(), get_ref: get_ref(), get_or: get_or(), expect: expect(), must: must(), keys: keys(), values: values(), values: VALUES);
+    }
+
+    #[test]
+    fn test_split() {
+        assert_eq!(SplitMaps::MAP.keys, ["a", "c"]);
+        assert_eq!(SplitMaps::MAP.values, [1, 3]);
+        assert_eq!(SplitMaps::LEN, 2);
+        assert_eq!(SplitMaps::get("c"), Some(3));
+        assert_eq!(SplitMaps::get("b"), None);
+        assert!(SplitMaps::has("a"));
+        assert_eq!(SplitMaps::try_get("b"), Err(crate::NotFound("b")));
+        assert_eq!(SplitMaps::get_key_value("a"), Some(("a", 1)));
+
+        const TWENTY: Option<u64> = SplitMaps::get_sorted(20);
+        assert_eq!(TWENTY, Some(200));
+        assert_eq!(SplitMaps::get_sorted(25), None);
+        assert_eq!(SplitMaps::SORTED.values[SplitMaps::position(30).unwrap()], 300);
+        assert_eq!(SplitMaps::get_ref(10), Some(&100));
+        assert_eq!(SplitMaps::get_or(0, 0), 0);
+        assert_eq!(SplitMaps::expect(10, "missing"), 100);
+        assert_eq!(SplitMaps::must(30), 300);
+        assert_eq!(SplitMaps::keys(), SplitMaps::SORTED.keys);
+        assert_eq!(SplitMaps::values(), SplitMaps::SORTED.values);
+        assert_eq!(SplitMaps::VALUES, SplitMaps::SORTED.values);
+
+        free_const_map!(static split FREE_SPLIT, free_split(), (u8 => char) {
+            1 => 'a',
+        });
+        static KEYS: &[u8; 1] = &FREE_SPLIT.keys;
+        assert_eq!(KEYS, &[1]);
+        assert_eq!(free_split(1), Some('a'));
+    }
+
+    struct Letters;
+
+    impl Letters {
+        const_map!(MAP, get(), rget(), (u8 => char) {
+            1 => 'a',
+            2 => 'b',
+            3 => 'a',
+        }, contains_key: has(), contains_value: has_value(), get_or: get_or(), get_ref: get_ref(), keys: keys(), values: values(), len: LEN, len: len(), min_key: min_key(), max_key: max_key(), position: position(),
+            keys: KEYS, values: VALUES, values_sorted: values_sorted(), inverse: INVERSE,
+            inverse_sorted: inverse_sorted(), require(1, 3), require(2));
+    }
+
+    #[test]
+    fn test_reverse() {
+        assert_eq!(Letters::get(2), Some('b'));
+        assert_eq!(Letters::rget('b'), Some(2));
+        assert_eq!(Letters::rget('a'), Some(1));
+        assert_eq!(Letters::rget('z'), None);
+    }
+
+    #[test]
+    fn test_contains_key() {
+        assert!(Letters::has(3));
+        assert!(!Letters::has(4));
+    }
+
+    #[test]
+    fn test_position() {
+        assert_eq!(Letters::position(1), Some(0));
+        assert_eq!(Letters::position(3), Some(2));
+        assert_eq!(Letters::position(4), None);
+        for (i, (k, _)) in Letters::MAP.iter().enumerate() {
+            assert_eq!(Letters::position(*k), Some(i));
+        }
+    }
+
+    #[test]
+    fn test_contains_value() {
+        assert!(Letters::has_value('b'));
+        assert!(!Letters::has_value('z'));
+        assert!(Numbers::has_value("two"));
+        assert!(!Numbers::has_value("four"));
+    }
+
+    #[test]
+    fn test_get_or() {
+        const C: char = Letters::get_or(9, '?');
+        assert_eq!(C, '?');
+        assert_eq!(Letters::get_or(2, '?'), 'b');
+        assert_eq!(Letters::get_ref(2), Some(&'b'));
+    }
+
+    #[test]
+    fn test_keys() {
+        assert_eq!(Letters::keys(), [1, 2, 3]);
+        assert_eq!(Letters::KEYS, Letters::keys());
+        assert_eq!(Letters::VALUES, ['a', 'b', 'a']);
+    }
+
+    #[test]
+    fn test_values() {
+        assert_eq!(Letters::values(), ['a', 'b', 'a']);
+        assert_eq!(Letters::values().len(), Letters::MAP.len());
+        assert_eq!(Letters::values_sorted(), ['a', 'a', 'b']);
+        assert_eq!(Flattened::FRUITS_SORTED, ["apple", "fig", "pear"]);
+        assert_eq!(Flattened::fruit(1), Some("pear"));
+    }
+
+    #[test]
+    fn test_inverse() {
+        assert_eq!(Letters::INVERSE, [('a', 1), ('b', 2), ('a', 3)]);
+        const SORTED: [(char, u8); 3] = Letters::inverse_sorted();
+        assert_eq!(SORTED, [('a', 1), ('a', 3), ('b', 2)]);
+        assert_eq!(SORTED.binary_search_by(|(v, _)| v.cmp(&'b')).map(|i| SORTED[i].1), Ok(2));
+    }
+
+    #[test]
+    fn test_len() {
+        const BUF: [u8; Letters::LEN] = [0; Letters::LEN];
+        assert_eq!(BUF.len(), 3);
+        assert_eq!(Letters::len(), 3);
+    }
+
+    struct ColorCodes;
+
+    impl ColorCodes {
+        const_map!(MAP, get(), rget(), (&'static str => u32) {
+            "red" => 1,
+            "green" => 2,
+            "blue" => 3,
+            "" => 4,
+        }, contains_key: has(), min_key: min_key(), max_key: max_key(),
+            all(Self::is_positive): all_positive(), any(Self::is_empty): any_empty(), any(Self::is_long): any_long(),
+            count(Self::is_odd): count_odd(), count(Self::is_long): count_long(), validate(Self::single_digits));
+
+        const fn is_positive(_: &str, v: u32) -> bool {
+            v > 0
+        }
+
+        const fn is_empty(k: &str, _: u32) -> bool {
+            k.is_empty()
+        }
+
+        const fn is_long(k: &str, _: u32) -> bool {
+            k.len() > 5
+        }
+
+        const fn is_odd(_: &str, v: u32) -> bool {
+            v % 2 == 1
+        }
+
+        const fn single_digits(entries: &[(&str, u32)]) -> bool {
+            let mut i = 0;
+            while i < entries.len() {
+                if entries[i].1 > 9 {
+                    return false;
+                }
+                i += 1;
+            }
+            true
+        }
+    }
+
+    const _: () = assert!(ColorCodes::all_positive());
+
+    #[test]
+    fn test_min_max_key() {
+        assert_eq!(Letters::min_key(), 1);
+        assert_eq!(Letters::max_key(), 3);
+        const MIN: &str = ColorCodes::min_key();
+        assert_eq!(MIN, "");
+        assert_eq!(ColorCodes::max_key(), "red");
+    }
+
+    #[test]
+    fn test_all_any() {
+        assert!(ColorCodes::all_positive());
+        assert!(ColorCodes::any_empty());
+        assert!(!ColorCodes::any_long());
+        const ODD: usize = ColorCodes::count_odd();
+        assert_eq!(ODD, 2);
+        assert_eq!(ColorCodes::count_long(), 0);
+    }
+
+    struct SortedStrs;
+
+    impl SortedStrs {
+        const_map!(sorted MAP, get(), (&'static str => u32) {
+            "" => 0,
+            "a" => 1,
+            "ab" => 2,
+            "b" => 3,
+        });
+    }
+
+    #[test]
+    fn test_str() {
+        const GREEN: Option<u32> = ColorCodes::get("green");
+        assert_eq!(GREEN, Some(2));
+        let bytes = *b"blue";
+        assert_eq!(ColorCodes::get(core::str::from_utf8(&bytes).unwrap()), Some(3));
+        assert_eq!(ColorCodes::get(""), Some(4));
+        assert_eq!(ColorCodes::get("gree"), None);
+        assert_eq!(ColorCodes::get("greens"), None);
+        assert!(ColorCodes::has("red"));
+        assert_eq!(ColorCodes::rget(1), Some("red"));
+
+        for (k, v) in SortedStrs::MAP {
+            assert_eq!(SortedStrs::get(k), Some(v));
+        }
+        assert_eq!(SortedStrs::get("aa"), None);
+        assert_eq!(SortedStrs::get("c"), None);
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Color {
+        name: &'static str,
+        rgb: [u8; 3],
+    }
+
+    struct BorrowedColors;
+
+    impl BorrowedColors {
+        const_map!(MAP, &get(), (u8 => Color) {
+            1 => Color { name: "red", rgb: [255, 0, 0] },
+            2 => Color { name: "green", rgb: [0, 255, 0] },
+        }, contains_key: has());
+    }
+
+    #[test]
+    fn test_by_ref() {
+        const GREEN: &Color = match BorrowedColors::get(2) {
+            Some(c) => c,
+            None => panic!("no green"),
+        };
+        assert_eq!(GREEN.name, "green");
+        assert_eq!(BorrowedColors::get(1), Some(&Color { name: "red", rgb: [255, 0, 0] }));
+        assert_eq!(BorrowedColors::get(3), None);
+        assert!(BorrowedColors::has(1));
+    }
+
+    /// Text formatted into a fixed buffer, so that `Display` implementations can be tested without
+    /// `alloc`.
+    struct Formatted {
+        buf: [u8; 64],
+        len: usize,
+    }
+
+    impl Formatted {
+        fn as_str(&self) -> &str {
+            core::str::from_utf8(&self.buf[..self.len]).unwrap()
+        }
+    }
+
+    impl core::fmt::Write for Formatted {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            let end = self.len + s.len();
+            self.buf.get_mut(self.len..end).ok_or(core::fmt::Error)?.copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    fn format(args: core::fmt::Arguments) -> Formatted {
+        let mut formatted = Formatted { buf: [0; 64], len: 0 };
+        core::fmt::Write::write_fmt(&mut formatted, args).unwrap();
+        formatted
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Op {
+        Add,
+        Sub,
+    }
+
+    struct Ops;
+
+    impl Ops {
+        const_map!(MAP, get(), (u8 => Op) {
+            b'+' => Op::Add,
+            b'-' => Op::Sub,
+        });
+    }
+
+    impl_try_from!(Ops::get, (u8 => Op));
+
+    #[test]
+    fn test_try_from() {
+        assert_eq!(Op::try_from(b'-'), Ok(Op::Sub));
+        let e = Op::try_from(b'*').unwrap_err();
+        assert_eq!(e, crate::NotFound(b'*'));
+        assert_eq!(format(format_args!("{e}")).as_str(), "key 42 not found");
+    }
+
+    struct Symbols;
+
+    impl Symbols {
+        const_map!(MAP, symbol(), (enum Op => &'static str) {
+            Op::Add => "+",
+            Op::Sub => "-",
+        }, total(2): symbol_total());
+    }
+
+    impl_display!(Symbols::symbol, Op);
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format(format_args!("{}", Op::Sub)).as_str(), "-");
+        assert_eq!(format(format_args!("{:<3}|{}", Op::Add, Op::Sub)).as_str(), "+  |-");
+        assert_eq!(Symbols::symbol_total(Op::Add), "+");
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Shape {
+        Circle(u8),
+        Square(u8),
+    }
+
+    const fn same_shape(a: &Shape, b: &Shape) -> bool {
+        match (a, b) {
+            (Shape::Circle(x), Shape::Circle(y)) | (Shape::Square(x), Shape::Square(y)) => *x == *y,
+            _ => false,
+        }
+    }
+
+    struct Shapes;
+
+    impl Shapes {
+        const_map!(MAP, shape(), id() using same_shape, (u16 => Shape) {
+            100 => Shape::Circle(1),
+            200 => Shape::Square(1),
+            300 => Shape::Circle(2),
+        }, reverse_using(same_shape): first_id());
+    }
+
+    #[test]
+    fn test_reverse_using() {
+        const SQUARE: Option<u16> = Shapes::id(Shape::Square(1));
+        assert_eq!(SQUARE, Some(200));
+        assert_eq!(Shapes::id(Shape::Circle(2)), Some(300));
+        assert_eq!(Shapes::id(Shape::Square(2)), None);
+        assert_eq!(Shapes::first_id(Shape::Circle(1)), Some(100));
+        assert_eq!(Shapes::shape(300), Some(Shape::Circle(2)));
+    }
+
+    struct Flattened;
+
+    impl Flattened {
+        const_map!(MAP, get(), (char => Option<u8>) {
+            'a' => Some(1),
+            'b' => None,
+        }, flatten: get_flat());
+
+        const_map!(split SPLIT, get_split(), (char => Option<u8>) {
+            'c' => Some(3),
+        }, flatten: get_split_flat());
+
+        const_map!(split FRUITS, fruit(), (u8 => &'static str) {
+            1 => "pear",
+            2 => "apple",
+            3 => "fig",
+        }, values_sorted: FRUITS_SORTED);
+    }
+
+    #[test]
+    fn test_flatten() {
+        assert_eq!(Flattened::get('b'), Some(None));
+        assert_eq!(Flattened::get('z'), None);
+        const A: Option<u8> = Flattened::get_flat('a');
+        assert_eq!(A, Some(1));
+        assert_eq!(Flattened::get_flat('b'), None);
+        assert_eq!(Flattened::get_flat('z'), None);
+        assert_eq!(Flattened::get_split_flat('c'), Some(3));
+        assert_eq!(Flattened::get_split_flat('a'), None);
+        assert_eq!(Flattened::get_split('c'), Some(Some(3)));
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum System {
+        Metric,
+        Imperial,
+    }
+
+    struct Countries;
+
+    impl Countries {
+        const_map!(MAP, system(), (&'static str => enum System) {
+            "fr" => System::Metric,
+            "us" => System::Imperial,
+        });
+
+        // Values can be other maps, or the entries of them.
+        const_map!(TABLES, table(), (enum System => &'static [(&'static str, u32)]) {
+            System::Metric => &System::METRIC,
+            System::Imperial => System::imperial_entries(),
+        });
+
+        const fn millimetres(country: &str, unit: &str) -> Option<u32> {
+            match Self::system(country) {
+                Some(system) => system.millimetres(unit),
+                None => None,
+            }
+        }
+    }
+
+    impl System {
+        const_map! {
+            METRIC, metric(), (&'static str => u32) {
+                "millimetre" => 1,
+                "metre" => 1000,
+            };
+            IMPERIAL, imperial(), (&'static str => u32) {
+                "inch" => 25,
+                "foot" => 305,
+            }, entries: imperial_entries();
+        }
+
+        const fn millimetres(self, unit: &str) -> Option<u32> {
+            match self {
+                System::Metric => Self::metric(unit),
+                System::Imperial => Self::imperial(unit),
+            }
+        }
+    }
+
+    #[test]
+    fn test_nested() {
+        const FOOT: Option<u32> = Countries::millimetres("us", "foot");
+        assert_eq!(FOOT, Some(305));
+        assert_eq!(Countries::millimetres("fr", "metre"), Some(1000));
+        assert_eq!(Countries::millimetres("fr", "foot"), None);
+        assert_eq!(Countries::millimetres("xx", "metre"), None);
+        const INCH: Option<&(&str, u32)> = match Countries::table(System::Imperial) {
+            Some(table) => table.first(),
+            None => None,
+        };
+        assert_eq!(INCH, Some(&("inch", 25)));
+        assert_eq!(Countries::table(System::Metric).map(<[_]>::len), Some(2));
+    }
+
+    struct Machine {
+        acc: i32,
+    }
+
+    fn op_inc(m: &mut Machine) {
+        m.acc += 1;
+    }
+
+    fn op_double(m: &mut Machine) {
+        m.acc *= 2;
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Instr {
+        Nop,
+        Halt,
+        Unknown(u8),
+    }
+
+    struct Decoder;
+
+    impl Decoder {
+        const_map!(OPS, op(), (u8 => fn(&mut Machine)) {
+            0x01 => op_inc,
+            0x02 => op_double,
+        }, contains_key: is_op(), get_or: op_or());
+
+        const_map!(INSTRS, instr(), (u8 => Instr) {
+            0x00 => Instr::Nop,
+            0xff => Instr::Halt,
+        }, or_else(Self::unknown): decode());
+
+        const_map!(sorted split SPLIT_INSTRS, split_instr(), (u8 => Instr) {
+            0x00 => Instr::Nop,
+        }, or_else(Instr::Unknown): split_decode());
+
+        const fn unknown(code: u8) -> Instr {
+            Instr::Unknown(code)
+        }
+
+        const_map!(jump HANDLERS, handler(), (u8 => for<'a> fn(&'a str) -> usize) {
+            0 => str::len,
+            1 => |s| s.chars().count(),
+        });
+    }
+
+    #[test]
+    fn test_fn_values() {
+        fn nop(_: &mut Machine) {}
+        let mut machine = Machine { acc: 1 };
+        for code in [0x01, 0x02, 0x03, 0x02] {
+            Decoder::op_or(code, nop)(&mut machine);
+        }
+        assert_eq!(machine.acc, 8);
+        const MISSING: Option<fn(&mut Machine)> = Decoder::op(0x03);
+        assert!(MISSING.is_none());
+        assert!(Decoder::is_op(0x02));
+        assert_eq!(Decoder::handler(0).map(|f| f("né")), Some(3));
+        assert_eq!(Decoder::handler(1).map(|f| f("né")), Some(2));
+    }
+
+    #[test]
+    fn test_or_else() {
+        const HALT: Instr = Decoder::decode(0xff);
+        assert_eq!(HALT, Instr::Halt);
+        assert_eq!(Decoder::decode(0x10), Instr::Unknown(0x10));
+        assert_eq!(Decoder::instr(0x10), None);
+        assert_eq!(Decoder::split_decode(0x00), Instr::Nop);
+        assert_eq!(Decoder::split_decode(0xff), Instr::Unknown(0xff));
+        assert_eq!(Decoder::split_instr(0x00), Some(Instr::Nop));
+    }
+
+    struct InternedMaps;
+
+    impl InternedMaps {
+        const_map! {
+            interned MAP[4], get(), (u8 => &'static str) {
+                1 => "odd",
+                2 => "even",
+                3 => "odd",
+                #[cfg(any())]
+                4 => "even",
+                5 => "odd",
+            }, get_ref: get_ref(), get_key_value: get_key_value(), or("none"): get_or_none(), must: must(),
+                keys: keys(), values: values(), len: LEN, require(5), contains_all: has_all(),
+                contains_any: has_any();
+            sorted interned SORTED, get_sorted(), (char => u8) {
+                'a' => 1,
+                'b' => 1,
+                'c' => 2,
+            }, position: position();
+            jump interned JUMP, get_jump(), (u8 => u64) {
+                0 => 0,
+                1 => u64::MAX,
+                2 => 0,
+            };
+            match interned MATCH, get_match(), (u8 => char) {};
+        }
+    }
+
+    #[test]
+    fn test_interned() {
+        assert_eq!(InternedMaps::MAP.entries, [(1, 0), (2, 1), (3, 0), (5, 0)]);
+        assert_eq!(InternedMaps::MAP.values, ["odd", "even"]);
+        const FIVE: Option<&str> = InternedMaps::get(5);
+        assert_eq!(FIVE, Some("odd"));
+        assert_eq!(InternedMaps::get(4), None);
+        assert_eq!(InternedMaps::get_ref(2), Some(&"even"));
+        assert_eq!(InternedMaps::get_key_value(3), Some((3, "odd")));
+        assert_eq!(InternedMaps::get_or_none(6), "none");
+        assert_eq!(InternedMaps::must(1), "odd");
+        assert_eq!(InternedMaps::keys(), [1, 2, 3, 5]);
+        assert_eq!(InternedMaps::values(), ["odd", "even", "odd", "odd"]);
+        assert_eq!(InternedMaps::LEN, 4);
+        assert_eq!(InternedMaps::get_sorted('b'), Some(1));
+        assert_eq!(InternedMaps::position('c'), Some(2));
+        assert_eq!(InternedMaps::SORTED.values, [1, 2]);
+        assert_eq!(InternedMaps::get_jump(1), Some(u64::MAX));
+        assert_eq!(InternedMaps::get_jump(3), None);
+        assert_eq!(InternedMaps::JUMP.values.len(), 2);
+        assert_eq!(InternedMaps::get_match(1), None);
+        assert_eq!(InternedMaps::MATCH.values, []);
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Class {
+        Digit,
+        Space,
+        Other,
+    }
+
+    struct Defaults;
+
+    impl Defaults {
+        const_map! {
+            match MAP, class(), (u8 => Class) {
+                b'0' => Class::Digit,
+                b'1' => Class::Digit,
+                b' ' => Class::Space,
+                _ => Class::Other,
+            }, contains_key: has(), len: LEN;
+            sorted SORTED, #[inline] class_sorted(), (&'static str => u8) {
+                "one" => 1,
+                "two" => 2,
+                /// Anything else.
+                _ => 0
+            };
+            split SPLIT, class_split() [binary], (u32 => char) {
+                1 => 'a',
+                _ => '?',
+            };
+            interned INTERNED, class_interned(), (u8 => enum Class) {
+                0 => Class::Space,
+                1 => Class::Space,
+                _ => Class::Other,
+            };
+        }
+    }
+
+    #[test]
+    fn test_default_entry() {
+        const ONE: Class = Defaults::class(b'1');
+        assert_eq!(ONE, Class::Digit);
+        assert_eq!(Defaults::class(b' '), Class::Space);
+        assert_eq!(Defaults::class(b'x'), Class::Other);
+        assert!(!Defaults::has(b'x'));
+        assert_eq!(Defaults::LEN, 3);
+        assert_eq!(Defaults::class_sorted("two"), 2);
+        assert_eq!(Defaults::class_sorted("three"), 0);
+        assert_eq!(Defaults::SORTED.len(), 2);
+        assert_eq!(Defaults::class_split(1), 'a');
+        assert_eq!(Defaults::class_split(2), '?');
+        assert_eq!(Defaults::class_interned(1), Class::Space);
+        assert_eq!(Defaults::class_interned(2), Class::Other);
+        assert_eq!(Defaults::INTERNED.values.len(), 1);
+    }
+
+    struct Settings;
+
+    impl Settings {
+        const_map! {
+            BASE, base(), (&'static str => u32) {
+                "width" => 80,
+                "height" => 24,
+                "tabs" => 8,
+            };
+            USER, user() fallback Self::base, (&'static str => u32) {
+                "tabs" => 4,
+            };
+            split SESSION, #[inline] session() fallback Self::user, (&'static str => u32) {
+                "width" => 120,
+            }, fallback(Self::base): session_base();
+            interned SCREEN, screen(), (&'static str => u32) {
+                "height" => 50,
+            }, fallback(Self::session): screen_session();
+        }
+    }
+
+    #[test]
+    fn test_fallback() {
+        const TABS: Option<u32> = Settings::user("tabs");
+        assert_eq!(TABS, Some(4));
+        assert_eq!(Settings::user("width"), Some(80));
+        assert_eq!(Settings::user("depth"), None);
+        assert_eq!(Settings::session("width"), Some(120));
+        assert_eq!(Settings::session("tabs"), Some(4));
+        assert_eq!(Settings::session_base("tabs"), Some(8));
+        assert_eq!(Settings::screen_session("height"), Some(50));
+        assert_eq!(Settings::screen_session("width"), Some(120));
+        assert_eq!(Settings::screen("width"), None);
+    }
+
+    struct FnvMaps;
+
+    impl FnvMaps {
+        const_map! {
+            fnv MAP, get(), (&'static str => u16) {
+                "http" => 80,
+                "https" => 443,
+                #[cfg(any())]
+                "gopher" => 70,
+                "ssh" => 22,
+            }, contains_key: has(), position: position();
+            fnv CI, get_ci(), (ci &'static str => u16) {
+                "Host" => 1,
+                "Accept" => 2,
+            };
+            fnv BYTES, get_bytes(), (&'static [u8] => u8) {
+                b"\x7fELF" => 1,
+                b"MZ" => 2,
+            };
+            SELECTED, get_selected() [fnv], (char => u8) {
+                'a' => 1,
+                'b' => 2,
+            };
+            // These have the same hash, which doesn't matter here.
+            fnv WIDE, get_wide(), (u128 => u8) {
+                1 => 1,
+                (1 << 64) | 1 => 2,
+            };
+            fnv interned INTERNED, get_interned(), (&'static str => u16) {
+                "a" => 1,
+                "b" => 1,
+            };
+        }
+    }
+
+    #[test]
+    fn test_fnv() {
+        const HTTPS: Option<u16> = FnvMaps::get("https");
+        assert_eq!(HTTPS, Some(443));
+        assert_eq!(FnvMaps::get("ssh"), Some(22));
+        assert_eq!(FnvMaps::get("gopher"), None);
+        assert_eq!(FnvMaps::get("htt"), None);
+        assert!(FnvMaps::has("http"));
+        assert_eq!(FnvMaps::position("ssh"), Some(2));
+        assert_eq!(FnvMaps::get_ci("ACCEPT"), Some(2));
+        assert_eq!(FnvMaps::get_ci("host"), Some(1));
+        assert_eq!(FnvMaps::get_bytes(b"MZ"), Some(2));
+        assert_eq!(FnvMaps::get_bytes(b"ELF"), None);
+        assert_eq!(FnvMaps::get_selected('b'), Some(2));
+        assert_eq!(FnvMaps::get_wide((1 << 64) | 1), Some(2));
+        assert_eq!(FnvMaps::get_wide(1), Some(1));
+        assert_eq!(FnvMaps::get_interned("b"), Some(1));
+        assert_eq!(FnvMaps::get_interned("c"), None);
+    }
+
+    struct Hidden;
+
+    impl Hidden {
+        const_map! {
+            MAP, get(), rget(), (u8 => char) {
+                1 => 'a',
+                2 => 'b',
+            }, len: LEN, doc_hidden, contains_key: has(), #[allow(dead_code)] keys: KEYS,
+                require(1), first: first(), unique_values;
+            OTHER, get_other(), (u8 => char) {
+                3 => 'c',
+            }, doc_hidden, len: OTHER_LEN
+        }
+    }
+
+    #[test]
+    fn test_doc_hidden() {
+        assert_eq!(Hidden::get(1), Some('a'));
+        assert_eq!(Hidden::rget('b'), Some(2));
+        assert_eq!(Hidden::LEN, 2);
+        assert!(Hidden::has(2));
+        assert_eq!(Hidden::first(), Some((1, 'a')));
+        assert_eq!(Hidden::get_other(3), Some('c'));
+        assert_eq!(Hidden::OTHER_LEN, 1);
+    }
+
+    const fn trim(s: &str) -> &str {
+        s.trim_ascii()
+    }
+
+    struct Normalized;
+
+    impl Normalized {
+        const_map!(hashed PRIORITY, priority(), (&'static str => u8) {
+            "urgent" => 3,
+            "high" => 2,
+            #[cfg(any())]
+            "medium" => 1,
+            "low" => 0,
+            "none" => 0,
+        }, entries_in_order: priorities(), entries_in_order: PRIORITIES);
+
+        const_map!(MAP, color() normalize Self::canonical, (&'static str => u32) {
+            "gray" => 0x808080,
+            "red" => 0xff0000,
+        }, normalize(trim): trimmed(), contains_all: has_all(), contains_any: has_any());
+
+        const_map!(split SPLIT, split_color(), (&'static str => u32) {
+            "gray" => 0x808080,
+        }, normalize(trim): split_trimmed(), contains_all: split_has_all(), contains_any: split_has_any());
+
+        const fn canonical(name: &str) -> &str {
+            match trim(name).as_bytes() {
+                b"grey" => "gray",
+                _ => trim(name),
+            }
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        const GREY: Option<u32> = Normalized::color(" grey ");
+        assert_eq!(GREY, Some(0x808080));
+        assert_eq!(Normalized::color("red\n"), Some(0xff0000));
+        assert_eq!(Normalized::color("blue"), None);
+        assert_eq!(Normalized::trimmed(" red"), Some(0xff0000));
+        assert_eq!(Normalized::trimmed("grey"), None);
+        assert_eq!(Normalized::split_trimmed("gray "), Some(0x808080));
+        assert_eq!(Normalized::split_color("gray"), Some(0x808080));
+    }
+
+    #[test]
+    fn test_entries_in_order() {
+        let order = [("urgent", 3), ("high", 2), ("low", 0), ("none", 0)];
+        assert_eq!(Normalized::priorities(), order);
+        assert_eq!(Normalized::PRIORITIES, order);
+        assert_eq!(Normalized::PRIORITY, order);
+        assert_eq!(Normalized::priority("low"), Some(0));
+        assert_eq!(Normalized::priority("medium"), None);
+    }
+
+    #[test]
+    fn test_contains_all_any() {
+        const _: () = assert!(Normalized::has_all(&["gray", "red"]));
+        assert!(Normalized::has_all(&[]));
+        assert!(!Normalized::has_all(&["red", "blue"]));
+        assert!(Normalized::has_any(&["blue", "red"]));
+        assert!(!Normalized::has_any(&["blue", "grey"]));
+        assert!(!Normalized::has_any(&[]));
+
+        assert!(Normalized::split_has_all(&["gray"]));
+        assert!(!Normalized::split_has_all(&["gray", "red"]));
+        assert!(Normalized::split_has_any(&["red", "gray"]));
+        assert!(!Normalized::split_has_any(&["red"]));
+
+        assert!(InternedMaps::has_all(&[5, 1, 2, 3]));
+        assert!(!InternedMaps::has_all(&[1, 4]));
+        assert!(InternedMaps::has_any(&[4, 3]));
+        assert!(!InternedMaps::has_any(&[0, 4, 6]));
+    }
+
+    struct Selected;
+
+    impl Selected {
+        const_map! {
+            SQUARES, #[inline] &squares() [jump], (u8 => u16) {
+                1 => 1,
+                2 => 4,
+                3 => 9,
+            }, contains_key: is_small();
+            ordered CODES, code() [binary] or 0, (char => u8) {
+                'a' => 1,
+                'b' => 2,
+            };
+            split WORDS, word() [binary], (&'static str => u8) {
+                "one" => 1,
+                "two" => 2,
+            };
+        }
+    }
+
+    #[test]
+    fn test_select_algorithm() {
+        const NINE: Option<&u16> = Selected::squares(3);
+        assert_eq!(NINE, Some(&9));
+        assert_eq!(Selected::squares(4), None);
+        assert!(Selected::is_small(1));
+        assert_eq!(Selected::code('b'), 2);
+        assert_eq!(Selected::code('z'), 0);
+        assert_eq!(Selected::word("two"), Some(2));
+        assert_eq!(Selected::word("three"), None);
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Answer {
+        Yes,
+        No,
+    }
+
+    struct Answers;
+
+    impl Answers {
+        const_map!(MAP, get(), (bool => Answer) {
+            true => Answer::Yes,
+            false => Answer::No,
+        }, total(2): get_total());
+    }
+
+    impl_from!(Answers::get_total, (bool => Answer));
+
+    #[test]
+    fn test_total() {
+        const NO: Answer = Answers::get_total(false);
+        assert_eq!(NO, Answer::No);
+        assert_eq!(Answer::from(true), Answer::Yes);
+        assert_eq!(Answers::get(true), Some(Answer::Yes));
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Level {
+        Low,
+        High,
+    }
+
+    struct Levels;
+
+    impl Levels {
+        const_map!(MAP, get(), (ci &'static str => Level) {
+            "low" => Level::Low,
+            "high" => Level::High,
+        }, from_str: level_from_str());
+    }
+
+    impl_from_str!(Levels::get, Level);
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("high".parse::<Level>(), Ok(Level::High));
+        assert_eq!("LOW".parse(), Ok(Level::Low));
+        let e = "medium".parse::<Level>().unwrap_err();
+        assert_eq!(e, crate::ParseError);
+        assert_eq!(format(format_args!("{e}")).as_str(), "string not found in const map");
+        const HIGH: Option<Level> = Levels::level_from_str("HIGH");
+        assert_eq!(HIGH, Some(Level::High));
+        let bytes = *b"medium";
+        assert_eq!(Levels::level_from_str(core::str::from_utf8(&bytes).unwrap()), None);
+        assert_eq!(SplitMaps::number_from_str("c"), Some(3));
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Status {
+        Ok,
+        NotFound,
+        Unknown,
+    }
+
+    struct Statuses;
+
+    impl Statuses {
+        const_map!(sorted MAP, status() or Status::Unknown, code(), (u16 => enum Status) {
+            200 => Status::Ok,
+            404 => Status::NotFound,
+        }, or(Status::Ok): status_or_ok());
+
+        const_map!(split SPLIT, split_status() or Status::Unknown, (u16 => Status) {
+            200 => Status::Ok,
+        });
+    }
+
+    #[test]
+    fn test_or_default() {
+        const NOT_FOUND: Status = Statuses::status(404);
+        assert_eq!(NOT_FOUND, Status::NotFound);
+        assert_eq!(Statuses::status(500), Status::Unknown);
+        assert_eq!(Statuses::code(Status::Ok), Some(200));
+        assert_eq!(Statuses::status_or_ok(500), Status::Ok);
+        assert_eq!(Statuses::split_status(200), Status::Ok);
+        assert_eq!(Statuses::split_status(404), Status::Unknown);
+    }
+
+    struct CfgEntries;
+
+    impl CfgEntries {
+        const_map!(MAP, get(), (u8 => &'static str) {
+            /// The first entry.
+            1 => "one",
+            #[cfg(any())]
+            2 => "disabled",
+            #[cfg(all())]
+            2 => "two",
+            #[cfg(any())]
+            3 => "disabled",
+        }, keys: keys(), len: LEN, iter: iter(), entries: entries(), entries: ENTRIES);
+    }
+
+    #[test]
+    fn test_cfg() {
+        assert_eq!(CfgEntries::MAP, [(1, "one"), (2, "two")]);
+        assert_eq!(CfgEntries::keys(), [1, 2]);
+        assert_eq!(CfgEntries::LEN, 2);
+        assert_eq!(CfgEntries::get(2), Some("two"));
+        assert_eq!(CfgEntries::get(3), None);
+        assert!(CfgEntries::iter().eq(&CfgEntries::MAP));
+        const ENTRIES: &[(u8, &str)] = CfgEntries::entries();
+        assert_eq!(ENTRIES, &CfgEntries::MAP);
+        assert_eq!(CfgEntries::ENTRIES, ENTRIES);
+    }
+
+    struct DocEntries;
+
+    impl DocEntries {
+        const_map!(MAP, get(), (u8 => u8) {
+            /// Documented.
+            #[allow(clippy::identity_op)]
+            1 => 1 + 0,
+            /// Also documented,
+            /// over two lines.
+            2 => 4,
+        }, values: values());
+    }
+
+    #[test]
+    fn test_entry_attrs() {
+        assert_eq!(DocEntries::MAP, [(1, 1), (2, 4)]);
+        assert_eq!(DocEntries::values(), [1, 4]);
+        assert_eq!(DocEntries::get(2), Some(4));
+    }
+
+    struct Primes;
+
+    impl Primes {
+        const_map!(sorted MAP, get(), (u32 => &'static str) {
+            2 => "two",
+            3 => "three",
+            5 => "five",
+            7 => "seven",
+            11 => "eleven",
+        }, floor: floor(), reverse_unique: key_of(), unique_values, many: get_many());
+    }
+
+    #[test]
+    fn test_sorted() {
+        for (k, v) in Primes::MAP {
+            assert_eq!(Primes::get(k), Some(v));
+        }
+        for k in [0, 1, 4, 6, 8, 12, u32::MAX] {
+            assert_eq!(Primes::get(k), None);
+        }
+    }
+
+    #[test]
+    fn test_many() {
+        const FOUND: [Option<&str>; 3] = Primes::get_many([3, 4, 11]);
+        assert_eq!(FOUND, [Some("three"), None, Some("eleven")]);
+        assert_eq!(Primes::get_many([]), []);
+    }
+
+    #[test]
+    fn test_floor() {
+        assert_eq!(Primes::floor(0), None);
+        assert_eq!(Primes::floor(2), Some("two"));
+        assert_eq!(Primes::floor(6), Some("five"));
+        assert_eq!(Primes::floor(u32::MAX), Some("eleven"));
+        const F: Option<&str> = Primes::floor(10);
+        assert_eq!(F, Some("seven"));
+    }
+
+    #[test]
+    fn test_reverse_unique() {
+        for (k, v) in Primes::MAP {
+            assert_eq!(Primes::key_of(v), Some(k));
+        }
+        assert_eq!(Primes::key_of("four"), None);
+    }
+
+    struct MatchMaps;
+
+    impl MatchMaps {
+        const_map!(match MAP[4], get(), (i16 => &'static str) {
+            -1 => "minus one",
+            0 => "zero",
+            #[cfg(any())]
+            1 => "disabled",
+            2 => "two",
+            1000 => "thousand",
+        }, contains_key: has(), expect: expect());
+    }
+
+    #[test]
+    fn test_match() {
+        for (k, v) in MatchMaps::MAP {
+            assert_eq!(MatchMaps::get(k), Some(v));
+        }
+        for k in [-2, 1, 3, 999, i16::MAX] {
+            assert_eq!(MatchMaps::get(k), None);
+        }
+        const THOUSAND: &str = MatchMaps::expect(1000, "missing");
+        assert_eq!(THOUSAND, "thousand");
+        assert!(MatchMaps::has(2));
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Dir {
+        North,
+        East,
+        South,
+        West,
+    }
+
+    struct Opposites;
+
+    impl Opposites {
+        const_map!(sorted MAP, get(), rget(), (enum Dir => enum Dir) {
+            Dir::North => Dir::South,
+            Dir::East => Dir::West,
+            Dir::South => Dir::North,
+            Dir::West => Dir::East,
+        }, total(4): opposite());
+    }
+
+    #[test]
+    fn test_enum() {
+        for (k, v) in Opposites::MAP {
+            assert_eq!(Opposites::get(k), Some(v));
+            assert_eq!(Opposites::opposite(k), v);
+            assert_eq!(Opposites::rget(v), Some(k));
+        }
+        const WEST: Dir = Opposites::opposite(Dir::East);
+        assert_eq!(WEST, Dir::West);
+    }
+
+    struct Numbers;
+
+    impl Numbers {
+        const_map!(bijective sorted MAP, to_value(), to_key(), (u8 => &'static str) {
+            1 => "one",
+            2 => "two",
+            3 => "three",
+        }, contains_value: has_value(), try_get: try_get());
+    }
+
+    #[test]
+    fn test_bijective() {
+        for (k, v) in Numbers::MAP {
+            assert_eq!(Numbers::to_value(k), Some(v));
+            assert_eq!(Numbers::to_key(v), Some(k));
+        }
+        assert_eq!(Numbers::to_value(4), None);
+        assert_eq!(Numbers::to_key("four"), None);
+    }
+
+    #[test]
+    fn test_try_get() {
+        fn parse(n: u8) -> Result<&'static str, crate::NotFound<u8>> {
+            let name = Numbers::try_get(n)?;
+            Ok(name)
+        }
+        assert_eq!(parse(2), Ok("two"));
+        assert_eq!(parse(4), Err(crate::NotFound(4)));
+        let e = Numbers::try_get(5).unwrap_err();
+        assert_eq!(format(format_args!("{e}")).as_str(), "key 5 not found");
+    }
+
+    struct Greek;
+
+    impl Greek {
+        const_map!(ordered MAP, get(), (&'static str => u8) {
+            "alpha" => 1,
+            "beta" => 2,
+            "gamma" => 3,
+        });
+    }
+
+    #[test]
+    fn test_ordered() {
+        for (k, v) in Greek::MAP {
+            assert_eq!(Greek::get(k), Some(v));
+        }
+        assert_eq!(Greek::get("delta"), None);
+    }
+
+    struct JumpMaps;
+
+    impl JumpMaps {
+        const_map!(jump MAP, get(), (i8 => char) {
+            -2 => 'a',
+            -1 => 'b',
+            0 => 'c',
+            #[cfg(all())]
+            1 => 'd',
+        });
+
+        const_map! {
+            jump WIDE, get_wide(), (u128 => char) {
+                1 => 'a',
+                2 => 'b',
+            };
+            jump WIDE_END, get_wide_end(), (u128 => char) {
+                u128::MAX - 1 => 'y',
+                u128::MAX => 'z',
+            };
+            jump SIGNED, get_signed(), (i128 => char) {
+                i128::MIN => 'a',
+                i128::MIN + 1 => 'b',
+            };
+            jump SIGNED_END, get_signed_end(), (i128 => char) {
+                i128::MAX - 1 => 'y',
+                i128::MAX => 'z',
+            };
+        }
+    }
+
+    #[test]
+    fn test_jump() {
+        for (k, v) in JumpMaps::MAP {
+            assert_eq!(JumpMaps::get(k), Some(v));
+        }
+        for k in [i8::MIN, -3, 2, i8::MAX] {
+            assert_eq!(JumpMaps::get(k), None);
+        }
+        assert_eq!(JumpMaps::get_wide(2), Some('b'));
+        for k in [0, 3, 1 << 127, u128::MAX] {
+            assert_eq!(JumpMaps::get_wide(k), None);
+        }
+        assert_eq!(JumpMaps::get_wide_end(u128::MAX), Some('z'));
+        for k in [0, 1, u128::MAX - 2] {
+            assert_eq!(JumpMaps::get_wide_end(k), None);
+        }
+        assert_eq!(JumpMaps::get_signed(i128::MIN), Some('a'));
+        assert_eq!(JumpMaps::get_signed(i128::MIN + 1), Some('b'));
+        for k in [i128::MIN + 2, -1, 0, i128::MAX] {
+            assert_eq!(JumpMaps::get_signed(k), None);
+        }
+        assert_eq!(JumpMaps::get_signed_end(i128::MAX), Some('z'));
+        for k in [i128::MIN, -1, 0, i128::MAX - 2] {
+            assert_eq!(JumpMaps::get_signed_end(k), None);
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Tagged {
+        id: u8,
+        tag: char,
+    }
+
+    const fn same_id(a: &Tagged, b: &Tagged) -> bool {
+        a.id == b.id
+    }
+
+    const fn same_len(a: &&str, b: &&str) -> bool {
+        a.len() == b.len()
+    }
+
+    struct CustomEq;
+
+    impl CustomEq {
+        const_map!(MAP, get() using same_id, (Tagged => u8) {
+            Tagged { id: 1, tag: 'a' } => 10,
+            Tagged { id: 2, tag: 'b' } => 20,
+        }, contains_key: has());
+
+        const_map!(BY_LEN, &by_len() using same_len, (&'static str => u8) {
+            "a" => 1,
+            "bb" => 2,
+        });
+    }
+
+    #[test]
+    fn test_using() {
+        assert_eq!(CustomEq::get(Tagged { id: 2, tag: 'z' }), Some(20));
+        assert_eq!(CustomEq::get(Tagged { id: 3, tag: 'a' }), None);
+        assert!(CustomEq::has(Tagged { id: 1, tag: 'b' }));
+        assert_eq!(CustomEq::by_len("xy"), Some(&2));
+        assert_eq!(CustomEq::by_len("xyz"), None);
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Name(&'static str);
+
+    const fn same_name(a: &Name, b: &Name) -> bool {
+        crate::cmp::str_eq(a.0, b.0)
+    }
+
+    struct RefKeys;
+
+    impl RefKeys {
+        const_map!(MAP, get() using same_name, (ref Name => u8) {
+            Name("one") => 1,
+            Name("two") => 2,
+        }, contains_key: has());
+
+        const_map!(sorted SORTED, get_sorted(), (ref u64 => char) {
+            1 => 'a',
+            5 => 'b',
+            9 => 'c',
+        }, get_or: get_or());
+    }
+
+    #[test]
+    fn test_by_ref_key() {
+        assert_eq!(RefKeys::get(&Name("two")), Some(2));
+        assert_eq!(RefKeys::get(&Name("three")), None);
+        assert!(RefKeys::has(&Name("one")));
+        for (k, v) in RefKeys::SORTED {
+            assert_eq!(RefKeys::get_sorted(&k), Some(v));
+        }
+        assert_eq!(RefKeys::get_or(&4, 'z'), 'z');
+    }
+
+    struct Duplicates;
+
+    impl Duplicates {
+        const_map!(multi MAP, get(), (char => u8) {
+            'a' => 1,
+            'b' => 2,
+            'a' => 3,
+            'a' => 4,
+        }, get_all: get_all());
+    }
+
+    impl Duplicates {
+        const_map!(allow_duplicates SHADOWED, get_shadowed(), (u8 => &'static str) {
+            0 => "zero",
+            1 => "one",
+            0 => "nothing",
+        });
+
+        const_map!(PARITY, parity(), (u8 => &'static str) {
+            1 => "odd",
+            2 => "even",
+            3 => "odd",
+            5 => "odd",
+        }, keys_for: keys_for());
+    }
+
+    #[test]
+    fn test_multi() {
+        assert_eq!(Duplicates::get('a'), Some(1));
+        let mut out = [0; 4];
+        assert_eq!(Duplicates::get_all('a', &mut out), 3);
+        assert_eq!(out, [1, 3, 4, 0]);
+        let mut out = [0; 2];
+        assert_eq!(Duplicates::get_all('a', &mut out), 3);
+        assert_eq!(out, [1, 3]);
+        assert_eq!(Duplicates::get_all('c', &mut out), 0);
+        assert_eq!(Duplicates::get_shadowed(0), Some("zero"));
+        assert_eq!(Duplicates::SHADOWED.len(), 3);
+        const B: ([u8; 1], usize) = {
+            let mut out = [0];
+            let n = Duplicates::get_all('b', &mut out);
+            (out, n)
+        };
+        assert_eq!(B, ([2], 1));
+    }
+
+    #[test]
+    fn test_keys_for() {
+        let mut out = [0; 4];
+        assert_eq!(Duplicates::keys_for("odd", &mut out), 3);
+        assert_eq!(out, [1, 3, 5, 0]);
+        let mut out = [0; 1];
+        assert_eq!(Duplicates::keys_for("odd", &mut out), 3);
+        assert_eq!(out, [1]);
+        assert_eq!(Duplicates::keys_for("none", &mut out), 0);
+        const EVEN: ([u8; 2], usize) = {
+            let mut out = [0; 2];
+            let n = Duplicates::keys_for("even", &mut out);
+            (out, n)
+        };
+        assert_eq!(EVEN, ([2, 0], 1));
+        assert_eq!(Duplicates::parity(5), Some("odd"));
+    }
+
+    struct Headers;
+
+    impl Headers {
+        const_map!(MAP, get(), (ci &'static str => u8) {
+            "Content-Type" => 1,
+            "Accept" => 2,
+        }, get_key_value: get_key_value(), canonicalize: canonical(), is_known: is_known());
+
+        const_map!(split SPLIT, get_split(), (ci &'static str => u8) {
+            "Host" => 3,
+        }, canonicalize: split_canonical(), is_known: split_is_known());
+
+        const_map!(interned INTERNED, get_interned(), (ci &'static str => u8) {
+            "Age" => 4,
+            "Date" => 4,
+        }, canonicalize: interned_canonical(), is_known: interned_is_known());
+
+        const_map!(sorted SORTED, get_sorted(), (ci &'static str => u8) {
+            "apple" => 1,
+            "Banana" => 2,
+            "cherry" => 3,
+        });
+    }
+
+    #[test]
+    fn test_ignore_case() {
+        assert_eq!(Headers::get("content-type"), Some(1));
+        assert_eq!(Headers::get("ACCEPT"), Some(2));
+        assert_eq!(Headers::get("Accept-Encoding"), None);
+        assert_eq!(Headers::get_key_value("CONTENT-type"), Some(("Content-Type", 1)));
+        assert_eq!(Headers::get_key_value("Accept-Encoding"), None);
+        const CANONICAL: Option<&str> = Headers::canonical("content-TYPE");
+        assert_eq!(CANONICAL, Some("Content-Type"));
+        assert_eq!(Headers::canonical("Accept-Encoding"), None);
+        assert!(Headers::is_known("accept"));
+        assert!(!Headers::is_known("Host"));
+        assert_eq!(Headers::split_canonical("HOST"), Some("Host"));
+        assert!(Headers::split_is_known("host"));
+        assert_eq!(Headers::get_split("hOsT"), Some(3));
+        assert_eq!(Headers::interned_canonical("date"), Some("Date"));
+        assert!(!Headers::interned_is_known("Accept"));
+        assert_eq!(Headers::get_interned("AGE"), Some(4));
+        for (k, v) in Headers::SORTED {
+            assert_eq!(Headers::get_sorted(k), Some(v));
+        }
+        assert_eq!(Headers::get_sorted("BANANA"), Some(2));
+        assert_eq!(Headers::get_sorted("Cherr"), None);
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Format {
+        Png,
+        Gif,
+        Pdf,
+    }
+
+    struct Formats;
+
+    impl Formats {
+        const_map!(MAP, get(), (&'static [u8] => Format) {
+            b"\x89PNG" => Format::Png,
+            b"GIF8" => Format::Gif,
+            b"%PDF" => Format::Pdf,
+        });
+
+        const_map!(hashed MAGIC, magic(), (&'static [u8] => Format) {
+            b"\x89PNG" => Format::Png,
+            b"GIF87a" => Format::Gif,
+            b"GIF89a" => Format::Gif,
+            b"%PDF" => Format::Pdf,
+            b"" => Format::Pdf,
+        }, contains_key: is_magic());
+
+        const_map!(sorted BY_NAME, by_name(), rget(), (&'static str => &'static [u8]) {
+            "gif" => b"GIF8",
+            "pdf" => b"%PDF",
+        });
+    }
+
+    #[test]
+    fn test_bytes() {
+        assert_eq!(Formats::get(b"%PDF"), Some(Format::Pdf));
+        assert_eq!(Formats::get(&b"\x89PNG\r\n"[..4]), Some(Format::Png));
+        assert_eq!(Formats::get(b"GIF"), None);
+        assert_eq!(Formats::by_name("gif"), Some(&b"GIF8"[..]));
+        assert_eq!(Formats::rget(b"%PDF"), Some("pdf"));
+    }
+
+    #[test]
+    fn test_hashed_bytes() {
+        const GIF: Option<Format> = Formats::magic(b"GIF89a");
+        assert_eq!(GIF, Some(Format::Gif));
+        assert_eq!(Formats::magic(&b"\x89PNG\r\n"[..4]), Some(Format::Png));
+        assert_eq!(Formats::magic(b"GIF88a"), None);
+        assert_eq!(Formats::magic(b""), Some(Format::Pdf));
+        assert!(!Formats::is_magic(b"%PDF-1.7"));
+        for (k, v) in &Formats::MAGIC {
+            assert_eq!(Formats::magic(k), Some(*v));
+        }
+    }
+
+    free_const_map!(FREE[2], free_get(), free_rget(), (u8 => char) {
+        1 => 'x',
+        2 => 'y',
+    }, keys: free_keys());
+
+    free_const_map!(sorted FREE_SORTED, free_sorted_get(), (u8 => char) {
+        1 => 'x',
+        2 => 'y',
+    });
+
+    #[test]
+    fn test_free() {
+        assert_eq!(FREE, [(1, 'x'), (2, 'y')]);
+        assert_eq!(free_get(2), Some('y'));
+        assert_eq!(free_rget('x'), Some(1));
+        assert_eq!(free_keys(), [1, 2]);
+        assert_eq!(free_sorted_get(1), Some('x'));
+        assert_eq!(free_sorted_get(3), None);
+    }
+
+    free_const_map!(static FREE_STATIC, free_static(), (u8 => char) {
+        1 => 'a',
+        2 => 'b',
+    }, contains_value: free_static_has_value(), entries: FREE_STATIC_ENTRIES, unique_values; hashed static FREE_HASHED[2], free_hashed(), (u32 => u32) {
+        10 => 1,
+        20 => 2,
+    }; sorted static interned FREE_INTERNED, free_interned(), (u8 => u32) {
+        1 => 10,
+        2 => 10,
+    });
+
+    #[test]
+    fn test_static() {
+        static FIRST: &(u8, char) = &FREE_STATIC[0];
+        assert_eq!(*FIRST, (1, 'a'));
+        const B: Option<char> = free_static(2);
+        assert_eq!(B, Some('b'));
+        assert!(free_static_has_value('a'));
+        assert_eq!(FREE_STATIC_ENTRIES.len(), FREE_STATIC.len());
+        assert_eq!(free_hashed(20), Some(2));
+        assert_eq!(free_hashed(30), None);
+        assert_eq!(free_interned(2), Some(10));
+        assert_eq!(FREE_INTERNED.values, [10]);
+    }
+
+    struct SeveralMaps;
+
+    impl SeveralMaps {
+        const_map! {
+            A, get_a(), (u8 => char) {
+                1 => 'a',
+            }, contains_key: has_a(), len: A_LEN;
+            sorted B[2], get_b(), rget_b(), (u8 => char) {
+                1 => 'b',
+                2 => 'c',
+            }, unique_values
+        }
+    }
+
+    free_const_map!(FREE_A, free_a(), (u8 => u8) { 1 => 2 }; FREE_B, free_b(), (u8 => u8) { 3 => 4 }, array_type: FreeB;);
+
+    #[test]
+    fn test_several_maps() {
+        assert_eq!(SeveralMaps::get_a(1), Some('a'));
+        assert!(SeveralMaps::has_a(1));
+        assert_eq!(SeveralMaps::A_LEN, 1);
+        assert_eq!(SeveralMaps::get_b(2), Some('c'));
+        assert_eq!(SeveralMaps::rget_b('b'), Some(1));
+        assert_eq!(free_a(1), Some(2));
+        assert_eq!(free_b(3), Some(4));
+        let b: FreeB = FREE_B;
+        assert_eq!(b, [(3, 4)]);
+    }
+
+    #[test]
+    fn test_free_try_from() {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        struct Letter(char);
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        struct Digit(u8);
+
+        free_const_map!(try_from sorted static LETTERS, letter(), (u8 => Letter) {
+            1 => Letter('a'),
+            2 => Letter('b'),
+        }; try_from split DIGITS, digit(), (char => Digit) {
+            '1' => Digit(1),
+        });
+        assert_eq!(Letter::try_from(2), Ok(Letter('b')));
+        assert_eq!(letter(1), Some(Letter('a')));
+        assert_eq!(Digit::try_from('1'), Ok(Digit(1)));
+        let e = Digit::try_from('2').unwrap_err();
+        assert_eq!(format(format_args!("{e}")).as_str(), "key not found in const_map DIGITS");
+        assert_eq!(digit('1'), Some(Digit(1)));
+        assert_eq!(DIGITS.keys, ['1']);
+    }
+
+    struct Tuples;
+
+    impl Tuples {
+        const_map!(OPCODES, get(), ((u8, u8) => &'static str) {
+            (0, 0) => "nop",
+            (0, 1) => "load",
+            (1, 0) => "store",
+        }, contains_key: has());
+
+        const_map!(sorted SORTED, get_sorted(), ((u8, char, bool) => u32) {
+            (0, 'a', true) => 1,
+            (0, 'b', false) => 2,
+            (0, 'b', true) => 3,
+            (2, 'a', false) => 4,
+        }, min_key: min_key(), max_key: max_key());
+    }
+
+    #[test]
+    fn test_tuple_keys() {
+        assert_eq!(Tuples::get((0, 1)), Some("load"));
+        assert_eq!(Tuples::get((1, 0)), Some("store"));
+        assert_eq!(Tuples::get((1, 1)), None);
+        assert!(Tuples::has((0, 0)));
+        for (k, v) in Tuples::SORTED {
+            assert_eq!(Tuples::get_sorted(k), Some(v));
+        }
+        assert_eq!(Tuples::get_sorted((0, 'a', false)), None);
+        assert_eq!(Tuples::get_sorted((1, 'a', true)), None);
+        assert_eq!(Tuples::min_key(), (0, 'a', true));
+        assert_eq!(Tuples::max_key(), (2, 'a', false));
+    }
+
+    struct Floats;
+
+    impl Floats {
+        const_map!(MAP, get(), rget(), (f32 => f64) {
+            1.5 => 0.5,
+            f32::NAN => f64::NAN,
+            -0.0 => -1.0,
+            0.0 => 1.0,
+        });
+
+        const_map!(sorted SORTED, get_sorted(), (f64 => u8) {
+            f64::NEG_INFINITY => 0,
+            -0.0 => 1,
+            0.0 => 2,
+            2.5 => 3,
+            f64::NAN => 4,
+        });
+
+        const_map!(hashed HASHED, get_hashed(), (f64 => u8) {
+            -0.0 => 1,
+            0.0 => 2,
+            f64::NAN => 3,
+        });
+    }
+
+    #[test]
+    fn test_float_keys() {
+        assert_eq!(Floats::get(1.5), Some(0.5));
+        assert!(Floats::get(f32::NAN).unwrap().is_nan());
+        assert_eq!(Floats::get(-0.0), Some(-1.0));
+        assert_eq!(Floats::get(0.0), Some(1.0));
+        assert_eq!(Floats::get(2.0), None);
+        assert!(Floats::rget(f64::NAN).unwrap().is_nan());
+        assert_eq!(Floats::rget(-1.0).map(f32::to_bits), Some((-0.0f32).to_bits()));
+        for (k, v) in Floats::SORTED {
+            assert_eq!(Floats::get_sorted(k), Some(v));
+        }
+        assert_eq!(Floats::get_sorted(1.0), None);
+        assert_eq!(Floats::get_sorted(f64::INFINITY), None);
+        for (k, v) in Floats::HASHED {
+            assert_eq!(Floats::get_hashed(k), Some(v));
+        }
+        assert_eq!(Floats::get_hashed(1.0), None);
+    }
+
+    struct Ranges;
+
+    impl Ranges {
+        const_map!(CLASSES, class(), (range char => &'static str) {
+            'a'..='z' => "lower",
+            'A'..='Z' => "upper",
+            '0'..='9' => "digit",
+            '_'..='_' => "underscore",
+        }, contains_key: is_word(), require('q', '_'));
+
+        const_map!(sorted BLOCKS, block(), (range u32 => &'static str) {
+            0x0000..=0x007f => "Basic Latin",
+            0x0080..=0x00ff => "Latin-1 Supplement",
+            0x0370..=0x03ff => "Greek and Coptic",
+        }, floor: block_floor());
+    }
+
+    #[test]
+    fn test_range_keys() {
+        assert_eq!(Ranges::class('q'), Some("lower"));
+        assert_eq!(Ranges::class('A'), Some("upper"));
+        assert_eq!(Ranges::class('9'), Some("digit"));
+        assert_eq!(Ranges::class('_'), Some("underscore"));
+        assert_eq!(Ranges::class('-'), None);
+        assert!(!Ranges::is_word(' '));
+        assert_eq!(Ranges::CLASSES[0].0, 'a'..='z');
+
+        for c in [0, 0x41, 0x7f] {
+            assert_eq!(Ranges::block(c), Some("Basic Latin"));
+        }
+        const GREEK: Option<&str> = Ranges::block(0x3b1);
+        assert_eq!(GREEK, Some("Greek and Coptic"));
+        for c in [0x100, 0x36f, 0x400, u32::MAX] {
+            assert_eq!(Ranges::block(c), None);
+        }
+        assert_eq!(Ranges::block_floor(0x200), Some("Latin-1 Supplement"));
+    }
+
+    struct HashedMaps;
+
+    impl HashedMaps {
+        const_map!(hashed MAP, get(), (u64 => u8) {
+            1 => 0,
+            10 => 1,
+            100 => 2,
+            1_000 => 3,
+            10_000 => 4,
+            100_000 => 5,
+            1_000_000 => 6,
+            10_000_000 => 7,
+            100_000_000 => 8,
+            1_000_000_000 => 9,
+            u64::MAX => 10,
+        }, contains_key: has());
+
+        const_map!(hashed KEYWORDS, keyword(), (&'static str => u8) {
+            "as" => 0,
+            "break" => 1,
+            "const" => 2,
+            "continue" => 3,
+            "crate" => 4,
+            "else" => 5,
+            "enum" => 6,
+            "extern" => 7,
+            "false" => 8,
+            "fn" => 9,
+            "for" => 10,
+            "if" => 11,
+            "" => 12,
+        });
+
+        const_map!(hashed HEADERS, header(), (ci &'static str => u8) {
+            "Content-Type" => 0,
+            "Content-Length" => 1,
+            "Host" => 2,
+        });
+
+        const_map!(hashed WIDE, wide(), (u128 => u8) {
+            1 => 0,
+            0x1_0000_0000_0000_0001 => 1,
+            u128::MAX => 2,
+            u64::MAX as u128 => 3,
+        });
+
+        const_map!(hashed SIGNED, signed(), (i64 => u8) {
+            0 => 0,
+            -1 => 1,
+            1 => 2,
+            i64::MIN => 3,
+            i64::MAX => 4,
+        });
+    }
+
+    #[test]
+    fn test_hashed() {
+        for (k, v) in HashedMaps::MAP {
+            assert_eq!(HashedMaps::get(k), Some(v));
+        }
+        for k in [0, 2, 99, 101, u64::MAX - 1] {
+            assert_eq!(HashedMaps::get(k), None);
+        }
+        const FOUND: Option<u8> = HashedMaps::get(100_000);
+        assert_eq!(FOUND, Some(5));
+        assert!(HashedMaps::has(u64::MAX));
+    }
+
+    #[test]
+    fn test_hashed_str() {
+        for (k, v) in HashedMaps::KEYWORDS {
+            assert_eq!(HashedMaps::keyword(k), Some(v));
+        }
+        for k in ["As", "extern ", "while", "f"] {
+            assert_eq!(HashedMaps::keyword(k), None);
+        }
+        const FOUND: Option<u8> = HashedMaps::keyword("fn");
+        assert_eq!(FOUND, Some(9));
+        assert_eq!(HashedMaps::header("content-length"), Some(1));
+        assert_eq!(HashedMaps::header("HOST"), Some(2));
+        assert_eq!(HashedMaps::header("Accept"), None);
+    }
+
+    #[test]
+    fn test_hashed_wide() {
+        for (k, v) in HashedMaps::WIDE {
+            assert_eq!(HashedMaps::wide(k), Some(v));
+        }
+        for k in [0, 2, 0x1_0000_0000_0000_0000, u128::MAX - 1] {
+            assert_eq!(HashedMaps::wide(k), None);
+        }
+        for (k, v) in HashedMaps::SIGNED {
+            assert_eq!(HashedMaps::signed(k), Some(v));
+        }
+        for k in [-2, 2, i64::MIN + 1] {
+            assert_eq!(HashedMaps::signed(k), None);
+        }
+    }
+
+    // Const trait implementations don't parse on a stable compiler, so they're tested in a separate
+    // file.
+    #[cfg(feature = "const-trait")]
+    mod const_trait;
+
+    #[cfg(feature = "std")]
+    struct HashMaps;
+
+    #[cfg(feature = "std")]
+    impl HashMaps {
+        const_map!(MAP, get(), (&'static str => u32) {
+            "red" => 1,
+            "green" => 2,
+            "blue" => 3,
+        }, hashmap: hashmap());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hashmap() {
+        let map = HashMaps::hashmap();
+        assert_eq!(map.len(), HashMaps::MAP.len());
+        assert_eq!(map["green"], 2);
+        assert_eq!(HashMaps::get("red"), Some(1));
+    }
+
+    #[cfg(feature = "alloc")]
+    struct BTreeMaps;
+
+    #[cfg(feature = "alloc")]
+    impl BTreeMaps {
+        const_map!(MAP, get(), (&'static str => u32) {
+            "red" => 1,
+            "green" => 2,
+            "blue" => 3,
+            "" => 4,
+        }, btreemap: btreemap());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_btreemap() {
+        let map = BTreeMaps::btreemap();
+        assert!(map.keys().eq(["", "blue", "green", "red"].iter()));
+        assert_eq!(map.range("b".."h").count(), 2);
+        assert_eq!(BTreeMaps::get(""), Some(4));
+    }
+
+    #[cfg(feature = "serde")]
+    struct Serialized;
+
+    #[cfg(feature = "serde")]
+    impl Serialized {
+        const_map!(MAP, get(), (char => u32) {
+            'a' => 1,
+            'b' => 2,
+        }, serialize: serialize());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize() {
+        fn check<T: serde::Serialize>(_: &T) {}
+        let entries = Serialized::serialize();
+        check(&entries);
+        assert_eq!(entries.0, Serialized::MAP);
+        assert_eq!(Serialized::get('b'), Some(2));
+    }
+
+    #[cfg(feature = "json")]
+    struct Json;
+
+    #[cfg(feature = "json")]
+    impl Json {
+        const_map!(MAP, get(), (&'static str => Option<u8>) {
+            "one" => Some(1),
+            "none" => None,
+            "quote\"" => Some(3),
+        }, to_json: to_json());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_to_json() {
+        assert_eq!(Json::to_json(), r#"[["one",1],["none",null],["quote\"",3]]"#);
+        assert_eq!(Json::get("none"), Some(None));
+    }
+
+    #[cfg(feature = "hashbrown")]
+    struct Hashbrown;
+
+    #[cfg(feature = "hashbrown")]
+    impl Hashbrown {
+        const_map!(MAP, get(), (&'static str => u8) {
+            "one" => 1,
+            "two" => 2,
+        }, hashbrown: hashbrown());
+    }
+
+    #[cfg(feature = "hashbrown")]
+    #[test]
+    fn test_hashbrown() {
+        let mut map = Hashbrown::hashbrown();
+        assert_eq!(map.get("two"), Some(&2));
+        map.insert("three", 3);
+        assert_eq!(map.len(), 3);
+        assert_eq!(Hashbrown::get("one"), Some(1));
+    }
+
+    // This is more entries than the recursion limit allows for without the `proc-macro` feature.
+    #[cfg(feature = "proc-macro")]
+    struct BigMaps;
+
+    #[cfg(feature = "proc-macro")]
+    macro_rules! big_maps {
+        ($($entries:tt)*) => {
+            // Checking that the keys are unique compares every pair of them, which with the const
+            // `PartialEq` implementation takes long enough for the compiler to stop it by default.
+            #[allow(long_running_const_eval)]
+            impl BigMaps {
+                const_map!(MAP, get(), (u16 => u16) { $($entries)* });
+                const_map!(sorted SORTED, get_sorted(), (u16 => u16) { $($entries)* });
+                const_map!(hashed HASHED, get_hashed(), (u16 => u16) { $($entries)* });
+                const_map!(fnv FNV, get_fnv(), (u16 => u16) { $($entries)* });
+                const_map!(jump JUMP, get_jump(), (u16 => u16) { $($entries)* });
+            }
+        };
+    }
+
+    #[cfg(feature = "proc-macro")]
+    big_maps! {
+        0 => 1, 1 => 2, 2 => 3, 3 => 4, 4 => 5, 5 => 6, 6 => 7, 7 => 8, 8 => 9, 9 => 10, 10 => 11,
+        11 => 12, 12 => 13, 13 => 14, 14 => 15, 15 => 16, 16 => 17, 17 => 18, 18 => 19, 19 => 20,
+        20 => 21, 21 => 22, 22 => 23, 23 => 24, 24 => 25, 25 => 26, 26 => 27, 27 => 28, 28 => 29,
+        29 => 30, 30 => 31, 31 => 32, 32 => 33, 33 => 34, 34 => 35, 35 => 36, 36 => 37, 37 => 38,
+        38 => 39, 39 => 40, 40 => 41, 41 => 42, 42 => 43, 43 => 44, 44 => 45, 45 => 46, 46 => 47,
+        47 => 48, 48 => 49, 49 => 50, 50 => 51, 51 => 52, 52 => 53, 53 => 54, 54 => 55, 55 => 56,
+        56 => 57, 57 => 58, 58 => 59, 59 => 60, 60 => 61, 61 => 62, 62 => 63, 63 => 64, 64 => 65,
+        65 => 66, 66 => 67, 67 => 68, 68 => 69, 69 => 70, 70 => 71, 71 => 72, 72 => 73, 73 => 74,
+        74 => 75, 75 => 76, 76 => 77, 77 => 78, 78 => 79, 79 => 80, 80 => 81, 81 => 82, 82 => 83,
+        83 => 84, 84 => 85, 85 => 86, 86 => 87, 87 => 88, 88 => 89, 89 => 90, 90 => 91, 91 => 92,
+        92 => 93, 93 => 94, 94 => 95, 95 => 96, 96 => 97, 97 => 98, 98 => 99, 99 => 100, 100 => 101,
+        101 => 102, 102 => 103, 103 => 104, 104 => 105, 105 => 106, 106 => 107, 107 => 108,
+        108 => 109, 109 => 110, 110 => 111, 111 => 112, 112 => 113, 113 => 114, 114 => 115,
+        115 => 116, 116 => 117, 117 => 118, 118 => 119, 119 => 120, 120 => 121, 121 => 122,
+        122 => 123, 123 => 124, 124 => 125, 125 => 126, 126 => 127, 127 => 128, 128 => 129,
+        129 => 130, 130 => 131, 131 => 132, 132 => 133, 133 => 134, 134 => 135, 135 => 136,
+        136 => 137, 137 => 138, 138 => 139, 139 => 140, 140 => 141, 141 => 142, 142 => 143,
+        143 => 144, 144 => 145, 145 => 146, 146 => 147, 147 => 148, 148 => 149, 149 => 150,
+        150 => 151, 151 => 152, 152 => 153, 153 => 154, 154 => 155, 155 => 156, 156 => 157,
+        157 => 158, 158 => 159, 159 => 160, 160 => 161, 161 => 162, 162 => 163, 163 => 164,
+        164 => 165, 165 => 166, 166 => 167, 167 => 168, 168 => 169, 169 => 170, 170 => 171,
+        171 => 172, 172 => 173, 173 => 174, 174 => 175, 175 => 176, 176 => 177, 177 => 178,
+        178 => 179, 179 => 180, 180 => 181, 181 => 182, 182 => 183, 183 => 184, 184 => 185,
+        185 => 186, 186 => 187, 187 => 188, 188 => 189, 189 => 190, 190 => 191, 191 => 192,
+        192 => 193, 193 => 194, 194 => 195, 195 => 196, 196 => 197, 197 => 198, 198 => 199,
+        199 => 200, 200 => 201, 201 => 202, 202 => 203, 203 => 204, 204 => 205, 205 => 206,
+        206 => 207, 207 => 208, 208 => 209, 209 => 210, 210 => 211, 211 => 212, 212 => 213,
+        213 => 214, 214 => 215, 215 => 216, 216 => 217, 217 => 218, 218 => 219, 219 => 220,
+        220 => 221, 221 => 222, 222 => 223, 223 => 224, 224 => 225, 225 => 226, 226 => 227,
+        227 => 228, 228 => 229, 229 => 230, 230 => 231, 231 => 232, 232 => 233, 233 => 234,
+        234 => 235, 235 => 236, 236 => 237, 237 => 238, 238 => 239, 239 => 240, 240 => 241,
+        241 => 242, 242 => 243, 243 => 244, 244 => 245, 245 => 246, 246 => 247, 247 => 248,
+        248 => 249, 249 => 250, 250 => 251, 251 => 252, 252 => 253, 253 => 254, 254 => 255,
+        255 => 256, 256 => 257, 257 => 258, 258 => 259, 259 => 260, 260 => 261, 261 => 262,
+        262 => 263, 263 => 264, 264 => 265, 265 => 266, 266 => 267, 267 => 268, 268 => 269,
+        269 => 270, 270 => 271, 271 => 272, 272 => 273, 273 => 274, 274 => 275, 275 => 276,
+        276 => 277, 277 => 278, 278 => 279, 279 => 280, 280 => 281, 281 => 282, 282 => 283,
+        283 => 284, 284 => 285, 285 => 286, 286 => 287, 287 => 288, 288 => 289, 289 => 290,
+        290 => 291, 291 => 292, 292 => 293, 293 => 294, 294 => 295, 295 => 296, 296 => 297,
+        297 => 298, 298 => 299, 299 => 300, 300 => 301, 301 => 302, 302 => 303, 303 => 304,
+        304 => 305, 305 => 306, 306 => 307, 307 => 308, 308 => 309, 309 => 310, 310 => 311,
+        311 => 312, 312 => 313, 313 => 314, 314 => 315, 315 => 316, 316 => 317, 317 => 318,
+        318 => 319, 319 => 320, 320 => 321, 321 => 322, 322 => 323, 323 => 324, 324 => 325,
+        325 => 326, 326 => 327, 327 => 328, 328 => 329, 329 => 330, 330 => 331, 331 => 332,
+        332 => 333, 333 => 334, 334 => 335, 335 => 336, 336 => 337, 337 => 338, 338 => 339,
+        339 => 340, 340 => 341, 341 => 342, 342 => 343, 343 => 344, 344 => 345, 345 => 346,
+        346 => 347, 347 => 348, 348 => 349, 349 => 350, 350 => 351, 351 => 352, 352 => 353,
+        353 => 354, 354 => 355, 355 => 356, 356 => 357, 357 => 358, 358 => 359, 359 => 360,
+        360 => 361, 361 => 362, 362 => 363, 363 => 364, 364 => 365, 365 => 366, 366 => 367,
+        367 => 368, 368 => 369, 369 => 370, 370 => 371, 371 => 372, 372 => 373, 373 => 374,
+        374 => 375, 375 => 376, 376 => 377, 377 => 378, 378 => 379, 379 => 380, 380 => 381,
+        381 => 382, 382 => 383, 383 => 384, 384 => 385, 385 => 386, 386 => 387, 387 => 388,
+        388 => 389, 389 => 390, 390 => 391, 391 => 392, 392 => 393, 393 => 394, 394 => 395,
+        395 => 396, 396 => 397, 397 => 398, 398 => 399, 399 => 400, 400 => 401, 401 => 402,
+        402 => 403, 403 => 404, 404 => 405, 405 => 406, 406 => 407, 407 => 408, 408 => 409,
+        409 => 410, 410 => 411, 411 => 412, 412 => 413, 413 => 414, 414 => 415, 415 => 416,
+        416 => 417, 417 => 418, 418 => 419, 419 => 420, 420 => 421, 421 => 422, 422 => 423,
+        423 => 424, 424 => 425, 425 => 426, 426 => 427, 427 => 428, 428 => 429, 429 => 430,
+        430 => 431, 431 => 432, 432 => 433, 433 => 434, 434 => 435, 435 => 436, 436 => 437,
+        437 => 438, 438 => 439, 439 => 440, 440 => 441, 441 => 442, 442 => 443, 443 => 444,
+        444 => 445, 445 => 446, 446 => 447, 447 => 448, 448 => 449, 449 => 450, 450 => 451,
+        451 => 452, 452 => 453, 453 => 454, 454 => 455, 455 => 456, 456 => 457, 457 => 458,
+        458 => 459, 459 => 460, 460 => 461, 461 => 462, 462 => 463, 463 => 464, 464 => 465,
+        465 => 466, 466 => 467, 467 => 468, 468 => 469, 469 => 470, 470 => 471, 471 => 472,
+        472 => 473, 473 => 474, 474 => 475, 475 => 476, 476 => 477, 477 => 478, 478 => 479,
+        479 => 480, 480 => 481, 481 => 482, 482 => 483, 483 => 484, 484 => 485, 485 => 486,
+        486 => 487, 487 => 488, 488 => 489, 489 => 490, 490 => 491, 491 => 492, 492 => 493,
+        493 => 494, 494 => 495, 495 => 496, 496 => 497, 497 => 498, 498 => 499, 499 => 500,
+        500 => 501, 501 => 502, 502 => 503, 503 => 504, 504 => 505, 505 => 506, 506 => 507,
+        507 => 508, 508 => 509, 509 => 510, 510 => 511, 511 => 512, 512 => 513, 513 => 514,
+        514 => 515, 515 => 516, 516 => 517, 517 => 518, 518 => 519, 519 => 520, 520 => 521,
+        521 => 522, 522 => 523, 523 => 524, 524 => 525, 525 => 526, 526 => 527, 527 => 528,
+        528 => 529, 529 => 530, 530 => 531, 531 => 532, 532 => 533, 533 => 534, 534 => 535,
+        535 => 536, 536 => 537, 537 => 538, 538 => 539, 539 => 540, 540 => 541, 541 => 542,
+        542 => 543, 543 => 544, 544 => 545, 545 => 546, 546 => 547, 547 => 548, 548 => 549,
+        549 => 550, 550 => 551, 551 => 552, 552 => 553, 553 => 554, 554 => 555, 555 => 556,
+        556 => 557, 557 => 558, 558 => 559, 559 => 560, 560 => 561, 561 => 562, 562 => 563,
+        563 => 564, 564 => 565, 565 => 566, 566 => 567, 567 => 568, 568 => 569, 569 => 570,
+        570 => 571, 571 => 572, 572 => 573, 573 => 574, 574 => 575, 575 => 576, 576 => 577,
+        577 => 578, 578 => 579, 579 => 580, 580 => 581, 581 => 582, 582 => 583, 583 => 584,
+        584 => 585, 585 => 586, 586 => 587, 587 => 588, 588 => 589, 589 => 590, 590 => 591,
+        591 => 592, 592 => 593, 593 => 594, 594 => 595, 595 => 596, 596 => 597, 597 => 598,
+        598 => 599, 599 => 600, 600 => 601, 601 => 602, 602 => 603, 603 => 604, 604 => 605,
+        605 => 606, 606 => 607, 607 => 608, 608 => 609, 609 => 610, 610 => 611, 611 => 612,
+        612 => 613, 613 => 614, 614 => 615, 615 => 616, 616 => 617, 617 => 618, 618 => 619,
+        619 => 620, 620 => 621, 621 => 622, 622 => 623, 623 => 624, 624 => 625, 625 => 626,
+        626 => 627, 627 => 628, 628 => 629, 629 => 630, 630 => 631, 631 => 632, 632 => 633,
+        633 => 634, 634 => 635, 635 => 636, 636 => 637, 637 => 638, 638 => 639, 639 => 640,
+        640 => 641, 641 => 642, 642 => 643, 643 => 644, 644 => 645, 645 => 646, 646 => 647,
+        647 => 648, 648 => 649, 649 => 650, 650 => 651, 651 => 652, 652 => 653, 653 => 654,
+        654 => 655, 655 => 656, 656 => 657, 657 => 658, 658 => 659, 659 => 660, 660 => 661,
+        661 => 662, 662 => 663, 663 => 664, 664 => 665, 665 => 666, 666 => 667, 667 => 668,
+        668 => 669, 669 => 670, 670 => 671, 671 => 672, 672 => 673, 673 => 674, 674 => 675,
+        675 => 676, 676 => 677, 677 => 678, 678 => 679, 679 => 680, 680 => 681, 681 => 682,
+        682 => 683, 683 => 684, 684 => 685, 685 => 686, 686 => 687, 687 => 688, 688 => 689,
+        689 => 690, 690 => 691, 691 => 692, 692 => 693, 693 => 694, 694 => 695, 695 => 696,
+        696 => 697, 697 => 698, 698 => 699, 699 => 700, 700 => 701, 701 => 702, 702 => 703,
+        703 => 704, 704 => 705, 705 => 706, 706 => 707, 707 => 708, 708 => 709, 709 => 710,
+        710 => 711, 711 => 712, 712 => 713, 713 => 714, 714 => 715, 715 => 716, 716 => 717,
+        717 => 718, 718 => 719, 719 => 720, 720 => 721, 721 => 722, 722 => 723, 723 => 724,
+        724 => 725, 725 => 726, 726 => 727, 727 => 728, 728 => 729, 729 => 730, 730 => 731,
+        731 => 732, 732 => 733, 733 => 734, 734 => 735, 735 => 736, 736 => 737, 737 => 738,
+        738 => 739, 739 => 740, 740 => 741, 741 => 742, 742 => 743, 743 => 744, 744 => 745,
+        745 => 746, 746 => 747, 747 => 748, 748 => 749, 749 => 750, 750 => 751, 751 => 752,
+        752 => 753, 753 => 754, 754 => 755, 755 => 756, 756 => 757, 757 => 758, 758 => 759,
+        759 => 760, 760 => 761, 761 => 762, 762 => 763, 763 => 764, 764 => 765, 765 => 766,
+        766 => 767, 767 => 768, 768 => 769, 769 => 770, 770 => 771, 771 => 772, 772 => 773,
+        773 => 774, 774 => 775, 775 => 776, 776 => 777, 777 => 778, 778 => 779, 779 => 780,
+        780 => 781, 781 => 782, 782 => 783, 783 => 784, 784 => 785, 785 => 786, 786 => 787,
+        787 => 788, 788 => 789, 789 => 790, 790 => 791, 791 => 792, 792 => 793, 793 => 794,
+        794 => 795, 795 => 796, 796 => 797, 797 => 798, 798 => 799, 799 => 800, 800 => 801,
+        801 => 802, 802 => 803, 803 => 804, 804 => 805, 805 => 806, 806 => 807, 807 => 808,
+        808 => 809, 809 => 810, 810 => 811, 811 => 812, 812 => 813, 813 => 814, 814 => 815,
+        815 => 816, 816 => 817, 817 => 818, 818 => 819, 819 => 820, 820 => 821, 821 => 822,
+        822 => 823, 823 => 824, 824 => 825, 825 => 826, 826 => 827, 827 => 828, 828 => 829,
+        829 => 830, 830 => 831, 831 => 832, 832 => 833, 833 => 834, 834 => 835, 835 => 836,
+        836 => 837, 837 => 838, 838 => 839, 839 => 840, 840 => 841, 841 => 842, 842 => 843,
+        843 => 844, 844 => 845, 845 => 846, 846 => 847, 847 => 848, 848 => 849, 849 => 850,
+        850 => 851, 851 => 852, 852 => 853, 853 => 854, 854 => 855, 855 => 856, 856 => 857,
+        857 => 858, 858 => 859, 859 => 860, 860 => 861, 861 => 862, 862 => 863, 863 => 864,
+        864 => 865, 865 => 866, 866 => 867, 867 => 868, 868 => 869, 869 => 870, 870 => 871,
+        871 => 872, 872 => 873, 873 => 874, 874 => 875, 875 => 876, 876 => 877, 877 => 878,
+        878 => 879, 879 => 880, 880 => 881, 881 => 882, 882 => 883, 883 => 884, 884 => 885,
+        885 => 886, 886 => 887, 887 => 888, 888 => 889, 889 => 890, 890 => 891, 891 => 892,
+        892 => 893, 893 => 894, 894 => 895, 895 => 896, 896 => 897, 897 => 898, 898 => 899,
+        899 => 900, 900 => 901, 901 => 902, 902 => 903, 903 => 904, 904 => 905, 905 => 906,
+        906 => 907, 907 => 908, 908 => 909, 909 => 910, 910 => 911, 911 => 912, 912 => 913,
+        913 => 914, 914 => 915, 915 => 916, 916 => 917, 917 => 918, 918 => 919, 919 => 920,
+        920 => 921, 921 => 922, 922 => 923, 923 => 924, 924 => 925, 925 => 926, 926 => 927,
+        927 => 928, 928 => 929, 929 => 930, 930 => 931, 931 => 932, 932 => 933, 933 => 934,
+        934 => 935, 935 => 936, 936 => 937, 937 => 938, 938 => 939, 939 => 940, 940 => 941,
+        941 => 942, 942 => 943, 943 => 944, 944 => 945, 945 => 946, 946 => 947, 947 => 948,
+        948 => 949, 949 => 950, 950 => 951, 951 => 952, 952 => 953, 953 => 954, 954 => 955,
+        955 => 956, 956 => 957, 957 => 958, 958 => 959, 959 => 960, 960 => 961, 961 => 962,
+        962 => 963, 963 => 964, 964 => 965, 965 => 966, 966 => 967, 967 => 968, 968 => 969,
+        969 => 970, 970 => 971, 971 => 972, 972 => 973, 973 => 974, 974 => 975, 975 => 976,
+        976 => 977, 977 => 978, 978 => 979, 979 => 980, 980 => 981, 981 => 982, 982 => 983,
+        983 => 984, 984 => 985, 985 => 986, 986 => 987, 987 => 988, 988 => 989, 989 => 990,
+        990 => 991, 991 => 992, 992 => 993, 993 => 994, 994 => 995, 995 => 996, 996 => 997,
+        997 => 998, 998 => 999, 999 => 1000, 1000 => 1001, 1001 => 1002, 1002 => 1003, 1003 => 1004,
+        1004 => 1005, 1005 => 1006, 1006 => 1007, 1007 => 1008, 1008 => 1009, 1009 => 1010,
+        1010 => 1011, 1011 => 1012, 1012 => 1013, 1013 => 1014, 1014 => 1015, 1015 => 1016,
+        1016 => 1017, 1017 => 1018, 1018 => 1019, 1019 => 1020, 1020 => 1021, 1021 => 1022,
+        1022 => 1023, 1023 => 1024, 1024 => 1025, 1025 => 1026, 1026 => 1027, 1027 => 1028,
+        1028 => 1029, 1029 => 1030, 1030 => 1031, 1031 => 1032, 1032 => 1033, 1033 => 1034,
+        1034 => 1035, 1035 => 1036, 1036 => 1037, 1037 => 1038, 1038 => 1039, 1039 => 1040,
+    }
+
+    #[test]
+    #[cfg(feature = "proc-macro")]
+    fn test_big_maps() {
+        assert_eq!(BigMaps::MAP.len(), 1040);
+        for k in 0..1040 {
+            assert_eq!(BigMaps::get(k), Some(k + 1));
+            assert_eq!(BigMaps::get_sorted(k), Some(k + 1));
+            assert_eq!(BigMaps::get_hashed(k), Some(k + 1));
+            assert_eq!(BigMaps::get_fnv(k), Some(k + 1));
+            assert_eq!(BigMaps::get_jump(k), Some(k + 1));
+        }
+        const LAST: Option<u16> = BigMaps::get(1039);
+        assert_eq!(LAST, Some(1040));
+        assert_eq!(BigMaps::get(1040), None);
+        assert_eq!(BigMaps::get_sorted(u16::MAX), None);
+        assert_eq!(BigMaps::get_hashed(2000), None);
+        assert_eq!(BigMaps::get_fnv(1040), None);
+        assert_eq!(BigMaps::get_jump(1040), None);
+    }
+}
+
+/// ```compile_fail
+/// struct S<const V: i32>;
+/// impl<const V: i32> S<V> {
+///     const_map::const_map!(MAP, get(), (i32 => char) {
+///         1 => 'a',
+///         2 => 'b',
+///         3 => 'c',
+///         4 => 'd',
+///     });
+/// 
+///     pub const C: char = match Self::get(V) {
+///         Some(c) => c,
+///         None => panic!("not found"),
+///     };
+/// }
+/// let x = S::<5>::C;
+/// ```
+#[cfg(doctest)]
+fn test_generic_const_panic() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP, get(), (i32 => char) {
+///         1 => 'a',
+///         2 => 'b',
+///         1 => 'c',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_duplicate_key() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP, get(), (i32 => char) {
+///         1 => 'a',
+///         _ => '?',
+///         2 => 'b',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_default_entry_not_last() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP, get() or '!', (i32 => char) {
+///         1 => 'a',
+///         _ => '?',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_default_entry_with_default() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(sorted MAP, get(), (i32 => char) {
+///         1 => 'a',
+///         3 => 'b',
+///         2 => 'c',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_sorted_out_of_order() {}
+
+/// ```compile_fail
+/// struct S<const V: i32>;
+/// impl<const V: i32> S<V> {
+///     const_map::const_map!(MAP, get(), (i32 => char) {
+///         1 => 'a',
+///         2 => 'b',
+///     }, expect: expect());
+///
+///     pub const C: char = Self::expect(V, "not found");
+/// }
+/// let x = S::<5>::C;
+/// ```
+#[cfg(doctest)]
+fn test_expect_panic() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP, get(), (&'static str => char) {
+///         "a" => 'a',
+///         "b" => 'b',
+///         "a" => 'c',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_duplicate_str_key() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP, get(), (u8 => char) {
+///         0 => 'a',
+///         1 => 'b',
+///     }, total(256): get_total());
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_total_wrong_count() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP, get(), (u8 => char) {
+///         0 => 'a',
+///         1 => 'b',
+///     }, total(2): get_total());
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_total_missing_key() {}
+
+/// ```compile_fail
+/// #[derive(Clone, Copy)]
+/// enum E { A, B, C }
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP, get(), (enum E => char) {
+///         E::A => 'a',
+///         E::C => 'c',
+///     }, total(3): get_total());
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_enum_missing_variant() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(bijective MAP, get(), rget(), (u8 => char) {
+///         1 => 'a',
+///         2 => 'b',
+///         3 => 'a',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_bijective_duplicate_value() {}
 
-    impl S1 {
-        const_map!(MAP, map_get(), (char => &'static str) {
-            'a' => "apple",
-            'b' => "banana",
-            'c' => "clementine",
-            'd' => "durian",
-        });
-    }
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(bijective MAP, get(), (u8 => char) {
+///         1 => 'a',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_bijective_without_reverse() {}
 
-    pub struct S2<const TAG: char>;
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP, get(), (u8 => &'static str) {
+///         1 => "a",
+///         2 => "a",
+///     }, reverse_unique: rget());
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_reverse_unique_duplicate_value() {}
 
-    impl<const TAG: char> S2<TAG> {
-        pub const FRUIT: &'static str = match S1::map_get(TAG) {
-            Some(s) => s,
-            None => panic!("no fruit found"),
-        };
-    }
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP, get(), (u8 => u32) {
+///         1 => 100,
+///         2 => 101,
+///         3 => 100,
+///     }, unique_values);
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_unique_values_duplicate() {}
 
-    #[test]
-    fn test() {
-        assert_eq!(S1::map_get('b'), Some("banana"));
-        assert_eq!(S1::map_get('x'), None);
-    }
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(ordered hashed MAP, get(), (u32 => char) {
+///         1 => 'a',
+///         3 => 'b',
+///         2 => 'c',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_ordered_out_of_order() {}
 
-    #[test]
-    fn test_generic_const() {
-        assert_eq!(S2::<'d'>::FRUIT, "durian");
-    }
-}
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(jump MAP, get(), (u8 => char) {
+///         1 => 'a',
+///         2 => 'b',
+///         4 => 'c',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_jump_gap() {}
 
 /// ```compile_fail
-/// struct S<const V: i32>;
-/// impl<const V: i32> S<V> {
-///     const_map::const_map!(MAP, get(), (i32 => char) {
+/// struct S;
+/// impl S {
+///     const_map::const_map!(jump MAP, get(), (u128 => char) {
+///         u128::MAX => 'a',
+///         0 => 'b',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_jump_wraps_around() {}
+
+/// ```compile_fail
+/// const fn eq(a: &u8, b: &u8) -> bool {
+///     *a % 10 == *b % 10
+/// }
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP, get() using eq, (u8 => char) {
+///         1 => 'a',
+///         11 => 'b',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_using_duplicate_key() {}
+
+/// ```compile_fail
+/// const fn eq(a: &u8, b: &u8) -> bool {
+///     *a == *b
+/// }
+/// struct S;
+/// impl S {
+///     const_map::const_map!(sorted MAP, get() using eq, (u8 => char) {
+///         1 => 'a',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_using_sorted() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP, get(), (u8 => char) {}, min_key: min_key());
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_min_key_empty() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(hashed MAP, get(), (ref u32 => char) {
+///         1 => 'a',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_by_ref_key_hashed() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(multi sorted MAP, get(), (u32 => char) {
+///         1 => 'a',
+///         1 => 'b',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_multi_sorted() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP, get(), (ci &'static str => u8) {
+///         "Accept" => 1,
+///         "accept" => 2,
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_ignore_case_duplicate() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP[3], get(), (u8 => char) {
 ///         1 => 'a',
 ///         2 => 'b',
-///         3 => 'c',
-///         4 => 'd',
 ///     });
-/// 
-///     pub const C: char = match Self::get(V) {
-///         Some(c) => c,
-///         None => panic!("not found"),
-///     };
 /// }
-/// let x = S::<5>::C;
 /// ```
 #[cfg(doctest)]
-fn test_generic_const_panic() {}
+fn test_wrong_len() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP, get(), (range u8 => char) {
+///         0..=9 => 'a',
+///         10..=20 => 'b',
+///         20..=30 => 'c',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_overlapping_ranges() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(hashed MAP, get(), (range u8 => char) {
+///         0..=9 => 'a',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_range_key_hashed() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(sorted MAP, get(), (f64 => char) {
+///         0.0 => 'a',
+///         -0.0 => 'b',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_float_keys_out_of_order() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(jump MAP, get(), (f32 => char) {
+///         0.0 => 'a',
+///         1.0 => 'b',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_float_keys_jump() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP, get(), ((u8, u8) => char) {
+///         (1, 2) => 'a',
+///         (2, 1) => 'b',
+///         (1, 2) => 'c',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_duplicate_tuple_key() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP, get(), (u8 => char) {
+///         1 => 'a',
+///     }, array_type: Array);
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_array_type_in_impl() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(static MAP, get(), (u8 => char) {
+///         1 => 'a',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_static_in_impl() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(hashed MAP, get(), (&'static str => char) {
+///         "a" => 'a',
+///         "b" => 'b',
+///         "a" => 'c',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_hashed_duplicate_str_key() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(hashed MAP, get(), (u128 => char) {
+///         1 => 'a',
+///         // These two keys have the same hash.
+///         2 => 'b',
+///         0x1_0000_0000_0000_0001 => 'c',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_hashed_collision() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(ordered hashed MAP, get(), (u128 => char) {
+///         1 => 'a',
+///         2 => 'b',
+///         0x1_0000_0000_0000_0001 => 'c',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_ordered_hashed_collision() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP, get(), (char => u8) {
+///         'a' => 1,
+///     }, must: must());
+/// }
+///
+/// struct T<const C: char>;
+/// impl<const C: char> T<C> {
+///     const VALUE: u8 = S::must(C);
+/// }
+///
+/// let _ = T::<'b'>::VALUE;
+/// ```
+#[cfg(doctest)]
+fn test_must_missing_key() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(interned MAP, get(), (u8 => char) {
+///         1 => 'a',
+///     }, reverse: key_of());
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_interned_unsupported_option() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(hashed split MAP, get(), (u8 => char) {
+///         1 => 'a',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_split_hashed() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(split MAP, get(), (u8 => char) {
+///         1 => 'a',
+///     }, entries: entries());
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_split_unsupported_option() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP, get(), (&'static str => u8) {
+///         "a" => 1,
+///         #[cfg(any())]
+///         "default" => 0,
+///     }, require("default"));
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_missing_required_key() {}
+
+/// ```compile_fail
+/// const fn ascii(entries: &[(u8, &str)]) -> bool {
+///     let mut i = 0;
+///     while i < entries.len() {
+///         if !entries[i].1.is_ascii() {
+///             return false;
+///         }
+///         i += 1;
+///     }
+///     true
+/// }
+///
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP, get(), (u8 => &'static str) {
+///         1 => "one",
+///         2 => "två",
+///     }, validate(ascii));
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_failed_validator() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(sorted MAP, get() [hashed], (u8 => char) {
+///         1 => 'a',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_algorithm_chosen_twice() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(split MAP, get() [jump], (u8 => char) {
+///         1 => 'a',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_split_selected_algorithm() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP, get() [quadratic], (u8 => char) {
+///         1 => 'a',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_unknown_algorithm() {}
+
+/// ```compile_fail
+/// const fn same(a: &u8, b: &u8) -> bool {
+///     *a == *b
+/// }
+///
+/// struct S;
+/// impl S {
+///     const_map::const_map!(bijective MAP, get(), rget() using same, (char => u8) {
+///         'a' => 1,
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_bijective_reverse_using() {}
+
+/// ```compile_fail
+/// struct S;
+/// impl S {
+///     const_map::const_map!(MAP, get(), sorted key_of(), (u8 => char) {
+///         1 => 'b',
+///         2 => 'a',
+///     });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_reverse_sorted_out_of_order() {}
+
+/// ```compile_fail
+/// use const_map::const_map;
+/// struct S;
+/// impl S {
+///     const_map!(try_from MAP, get(), (u8 => char) { 1 => 'a' });
+/// }
+/// ```
+#[cfg(doctest)]
+fn test_try_from_in_impl() {}
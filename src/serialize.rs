@@ -0,0 +1,31 @@
+//! Serialization of const maps with serde, for the functions generated by the `serialize` and
+//! `to_json` options.
+
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+/// The entries of a const map, which can be serialized with serde as a map from keys to values.
+///
+/// This is returned by the functions generated by the `serialize` option of [`const_map!`], and
+/// needs the `serde` feature.
+///
+/// [`const_map!`]: crate::const_map
+#[derive(Debug, Clone, Copy)]
+pub struct Serializable<K: 'static, V: 'static>(pub &'static [(K, V)]);
+
+impl<K: Serialize, V: Serialize> Serialize for Serializable<K, V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in self.0 {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+/// Serializes the entries of a const map as a JSON array of `[key, value]` arrays, for the
+/// functions generated by the `to_json` option.
+#[cfg(feature = "json")]
+#[doc(hidden)]
+pub fn to_json<K: Serialize, V: Serialize>(entries: &[(K, V)]) -> crate::collections::String {
+    serde_json::to_string(entries).expect("couldn't serialize const_map entries")
+}
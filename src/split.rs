@@ -0,0 +1,11 @@
+/// The keys and values of a const map declared as `split`, stored in two arrays of the same
+/// length instead of one array of pairs, so that there's no padding between them.
+///
+/// The value for `keys[i]` is `values[i]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Split<K, V, const N: usize> {
+    /// The keys of the map, in order.
+    pub keys: [K; N],
+    /// The values of the map, in the same order as the keys.
+    pub values: [V; N],
+}
@@ -0,0 +1,30 @@
+#[derive(Clone, Copy, Debug)]
+struct Version {
+    major: u16,
+    minor: u16,
+}
+
+impl const PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.major == other.major && self.minor == other.minor
+    }
+}
+
+struct Versions;
+
+impl Versions {
+    const_map!(MAP, get(), (Version => &'static str) {
+        Version { major: 1, minor: 0 } => "first",
+        Version { major: 1, minor: 1 } => "patched",
+        Version { major: 2, minor: 0 } => "second",
+    }, reverse: version_of(), contains_key: has());
+}
+
+#[test]
+fn test_const_trait() {
+    const PATCHED: Option<&str> = Versions::get(Version { major: 1, minor: 1 });
+    assert_eq!(PATCHED, Some("patched"));
+    assert_eq!(Versions::get(Version { major: 3, minor: 0 }), None);
+    assert!(Versions::has(Version { major: 2, minor: 0 }));
+    assert_eq!(Versions::version_of("first"), Some(Version { major: 1, minor: 0 }));
+}
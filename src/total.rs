@@ -0,0 +1,31 @@
+//! The number of values of the key types which `total` maps can check that they have every value
+//! of.
+
+/// Implemented for `bool`, `char` and the integer types, which are the key types compared with
+/// `==`.
+pub trait Values {
+    /// How many values the type has, or `None` if it's too many for a map to have every one of
+    /// them.
+    const COUNT: Option<usize>;
+}
+
+impl Values for bool {
+    const COUNT: Option<usize> = Some(2);
+}
+
+impl Values for char {
+    // Every code point except the surrogates, from U+D800 to U+DFFF.
+    const COUNT: Option<usize> = Some(0x110000 - 0x800);
+}
+
+macro_rules! impl_values {
+    ($($ty:ty),*) => {
+        $(
+            impl Values for $ty {
+                const COUNT: Option<usize> = 1usize.checked_shl(<$ty>::BITS);
+            }
+        )*
+    };
+}
+
+impl_values!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);